@@ -0,0 +1,33 @@
+//! Fuzzing support via [`arbitrary`].
+//!
+//! This module is only available with the `arbitrary` feature. It implements
+//! [`Arbitrary`] for [`PartialArray`]s, so they can be part of fuzzer inputs.
+//! The first byte of the input selects the length, the elements follow.
+use crate::PartialArray;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a, T: Arbitrary<'a>, const N: usize> Arbitrary<'a> for PartialArray<T, N> {
+    /// Generate an arbitrary [`PartialArray`] with up to `N` elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// use arbitrary::{Arbitrary, Unstructured};
+    ///
+    /// let mut data = Unstructured::new(&[2, 3, 7, 8, 9, 10]);
+    /// let array = PartialArray::<u8, 4>::arbitrary(&mut data).unwrap();
+    /// assert!(array.len() <= 4);
+    /// ```
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = match u.int_in_range(0..=3_u8)? {
+            0 => 0,
+            1 => N,
+            _ => u.int_in_range(0..=N)?,
+        };
+        (0..len).map(|_| T::arbitrary(u)).collect()
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(<u8 as Arbitrary>::size_hint(depth), (0, None))
+    }
+}
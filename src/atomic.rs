@@ -0,0 +1,167 @@
+//! Lock-free appending to [`PartialArray`]s from multiple threads.
+//!
+//! This module is only available on targets supporting atomic operations on
+//! pointer-sized integers. It provides the [`AtomicPartialArray`], which allows
+//! to [`push`] elements via a shared reference, e.g. to collect results from
+//! worker threads into a bounded buffer without a mutex:
+//! ```
+//! # use partial_array::atomic::AtomicPartialArray;
+//! let results = AtomicPartialArray::<u32, 8>::new();
+//! std::thread::scope(|scope| {
+//!     for worker in 0..4 {
+//!         let results = &results;
+//!         scope.spawn(move || results.push(worker * worker).unwrap());
+//!     }
+//! });
+//!
+//! let mut results = results.into_partial_array();
+//! results.sort_unstable();
+//! assert_eq!(results, [0, 1, 4, 9]);
+//! ```
+//!
+//! [`push`]: AtomicPartialArray::push
+use crate::sync::{AtomicBool, AtomicUsize, Ordering, UnsafeCell};
+use crate::{CapacityError, PartialArray};
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+use core::{array, ptr};
+
+/// An append-only [`PartialArray`], that can be filled concurrently.
+///
+/// Each [`push`] reserves a slot by atomically incrementing an index, writes
+/// the element and marks the slot as ready. Therefore the elements are stored
+/// in the order of their reservation, which is not necessarily the order, in
+/// which the pushes complete. Elements can be read via [`get`] as soon as they
+/// are ready.
+///
+/// [`push`]: AtomicPartialArray::push
+/// [`get`]: AtomicPartialArray::get
+pub struct AtomicPartialArray<T, const N: usize> {
+    /// The number of reserved slots, which is never greater than `N`.
+    reserved: AtomicUsize,
+    /// Whether the slot with the same index is written (and initialized).
+    ready: [AtomicBool; N],
+    /// The storage for the items.
+    ///
+    /// A slot is only written by the thread, which reserved it, and only read
+    /// once it is marked as ready.
+    array: [UnsafeCell<MaybeUninit<T>>; N],
+}
+// SAFETY: the elements are owned by the array, so it can be sent to another
+// thread, if the elements can.
+unsafe impl<T: Send, const N: usize> Send for AtomicPartialArray<T, N> {}
+// SAFETY: a shared reference allows to move elements into the array from
+// multiple threads (requiring `Send`) and to share references to them between
+// threads (requiring `Sync`). Slots are synchronized via the ready flags.
+unsafe impl<T: Send + Sync, const N: usize> Sync for AtomicPartialArray<T, N> {}
+impl<T, const N: usize> AtomicPartialArray<T, N> {
+    /// Create an empty [`AtomicPartialArray`].
+    pub fn new() -> Self {
+        Self {
+            reserved: AtomicUsize::new(0),
+            ready: array::from_fn(|_| AtomicBool::new(false)),
+            array: array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+        }
+    }
+
+    /// The maximum number of elements (`N`).
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Append an element or return it, if the array is full.
+    ///
+    /// This never blocks: if another thread concurrently reserves the last
+    /// slot, this returns an error.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::atomic::AtomicPartialArray;
+    /// let array = AtomicPartialArray::<u8, 1>::new();
+    /// assert!(array.push(1).is_ok());
+    /// assert_eq!(array.push(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn push(&self, value: T) -> Result<(), CapacityError<T>> {
+        let mut index = self.reserved.load(Ordering::Relaxed);
+        loop {
+            if index >= N {
+                return Err(CapacityError::new(value));
+            }
+            match self.reserved.compare_exchange_weak(
+                index,
+                index + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => index = current,
+            }
+        }
+
+        self.array[index].with_mut(|slot| {
+            // SAFETY: the slot was exclusively reserved above and is not marked
+            // as ready yet, so no other thread accesses it.
+            unsafe { ptr::write(slot, MaybeUninit::new(value)) }
+        });
+        self.ready[index].store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Get the element at `index`, if it is already written.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if !self.ready.get(index)?.load(Ordering::Acquire) {
+            return None;
+        }
+
+        self.array[index].with(|slot| {
+            // SAFETY: the slot is marked as ready (which synchronizes with the
+            // write), so it is initialized and never written again while the
+            // array is shared.
+            Some(unsafe { (*slot).assume_init_ref() })
+        })
+    }
+
+    /// Convert into a [`PartialArray`] of the written elements.
+    ///
+    /// As this takes the array by value, all pushes are completed, so the
+    /// elements are in the order of their reservation.
+    pub fn into_partial_array(self) -> PartialArray<T, N> {
+        (0..N)
+            .filter(|&i| self.ready[i].swap(false, Ordering::Acquire))
+            .map(|i| {
+                self.array[i].with(|slot| {
+                    // SAFETY: the slot was ready, i.e. initialized. The ready
+                    // flag was reset, so it is neither read nor dropped again.
+                    unsafe { ptr::read(slot).assume_init() }
+                })
+            })
+            .collect()
+    }
+}
+impl<T, const N: usize> Default for AtomicPartialArray<T, N> {
+    /// Initialize an empty [`AtomicPartialArray`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Debug, const N: usize> Debug for AtomicPartialArray<T, N> {
+    /// Debug-format the elements, that are already written.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries((0..N).filter_map(|i| self.get(i)))
+            .finish()
+    }
+}
+impl<T, const N: usize> Drop for AtomicPartialArray<T, N> {
+    fn drop(&mut self) {
+        (0..N)
+            .filter(|&i| self.ready[i].swap(false, Ordering::Acquire))
+            .for_each(|i| {
+                self.array[i].with_mut(|slot| {
+                    // SAFETY: the slot was ready, i.e. initialized. The ready
+                    // flag was reset, so it is not dropped again.
+                    unsafe { ptr::drop_in_place((*slot).as_mut_ptr()) }
+                })
+            });
+    }
+}
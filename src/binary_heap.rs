@@ -0,0 +1,297 @@
+//! A fixed-capacity priority queue.
+//!
+//! This module provides the [`PartialBinaryHeap`], which is a binary heap
+//! stored in a [`PartialArray`]. It does not allocate, which makes it suitable
+//! e.g. for schedulers in firmware:
+//! ```
+//! use partial_array::binary_heap::{Min, PartialBinaryHeap};
+//!
+//! // (deadline, task)
+//! let mut timers = PartialBinaryHeap::<(u32, &str), 8, Min>::new();
+//! timers.push((300, "blink"));
+//! timers.push((100, "poll"));
+//! timers.push((200, "send"));
+//!
+//! assert_eq!(timers.pop(), Some((100, "poll")));
+//! assert_eq!(timers.peek(), Some(&(200, "send")));
+//! ```
+use crate::{heap, storage, CapacityError, PartialArray};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+/// The kind of a [`PartialBinaryHeap`]: either a [`Max`]- or a [`Min`]-heap.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Kind: private::Sealed {
+    /// Check, whether `a` has a lower priority than `b`.
+    #[doc(hidden)]
+    fn is_less<T: Ord>(a: &T, b: &T) -> bool;
+
+    /// Whether the heap order is the reversed order of the elements.
+    #[doc(hidden)]
+    const REVERSED: bool;
+}
+/// The kind of a max-heap: the greatest element is at the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Max;
+/// The kind of a min-heap: the smallest element is at the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Min;
+impl Kind for Max {
+    const REVERSED: bool = false;
+
+    fn is_less<T: Ord>(a: &T, b: &T) -> bool {
+        a < b
+    }
+}
+impl Kind for Min {
+    const REVERSED: bool = true;
+
+    fn is_less<T: Ord>(a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::Max {}
+    impl Sealed for super::Min {}
+}
+
+/// A priority queue for up to `N` elements, implemented as a binary heap.
+///
+/// By default this is a max-heap, i.e. [`pop`] returns the greatest element.
+/// Use [`Min`] as the third parameter for a min-heap.
+///
+/// [`pop`]: PartialBinaryHeap::pop
+pub struct PartialBinaryHeap<T, const N: usize, K = Max> {
+    /// The elements in heap order with respect to `K`.
+    data: PartialArray<T, N>,
+    kind: PhantomData<K>,
+}
+impl<T, const N: usize, K> PartialBinaryHeap<T, N, K> {
+    /// Create an empty [`PartialBinaryHeap`].
+    pub const fn new() -> Self {
+        Self {
+            data: PartialArray::new(),
+            kind: PhantomData,
+        }
+    }
+
+    /// The number of elements.
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Check, whether there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The maximum number of elements (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Get a reference to the top element or [`None`], if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// The elements in an unspecified (heap) order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Iterate over the elements in an unspecified (heap) order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Convert into a [`PartialArray`] in an unspecified (heap) order.
+    pub fn into_partial_array(self) -> PartialArray<T, N> {
+        self.data
+    }
+
+    /// Remove and drop all elements.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> PartialBinaryHeap<T, N, K> {
+    /// Insert an element.
+    ///
+    /// # Panics
+    /// Panics, if the heap is already full.
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            panic!("PartialBinaryHeap is full (capacity {})", N);
+        }
+    }
+
+    /// Insert an element or return it, if the heap is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        self.data.as_partial_slice_mut().try_push(value)?;
+        let index = self.data.len() - 1;
+        heap::sift_up(&mut self.data, index, &mut K::is_less);
+        Ok(())
+    }
+
+    /// Remove the top element and return it or [`None`], if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        if self.data.is_empty() {
+            return Some(last);
+        }
+
+        let top = mem::replace(&mut self.data[0], last);
+        heap::sift_down(&mut self.data, 0, &mut K::is_less);
+        Some(top)
+    }
+
+    /// Get a mutable reference to the top element or [`None`], if it is empty.
+    ///
+    /// The heap order is restored, when the returned guard is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::binary_heap::PartialBinaryHeap;
+    /// let mut heap: PartialBinaryHeap<u8, 4> = [1, 5, 3].iter().copied().collect();
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 0;
+    /// }
+    /// assert_eq!(heap.peek(), Some(&3));
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, N, K>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    /// Convert into a [`PartialArray`] sorted ascendingly.
+    ///
+    /// This is done in-place in O(N log N) time.
+    pub fn into_sorted_array(mut self) -> PartialArray<T, N> {
+        // the heap sort yields the elements in heap order (top element last)
+        heap::sort(&mut self.data, &mut K::is_less);
+        if K::REVERSED {
+            self.data.reverse();
+        }
+        self.data
+    }
+}
+impl<T, const N: usize, K> Default for PartialBinaryHeap<T, N, K> {
+    /// Initialize an empty [`PartialBinaryHeap`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone, const N: usize, K> Clone for PartialBinaryHeap<T, N, K> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            kind: PhantomData,
+        }
+    }
+}
+impl<T: Debug, const N: usize, K> Debug for PartialBinaryHeap<T, N, K> {
+    /// Debug-format the elements in an unspecified (heap) order.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.data, f)
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> From<PartialArray<T, N>> for PartialBinaryHeap<T, N, K> {
+    /// Build a heap from the elements of a [`PartialArray`] in O(N) time.
+    fn from(mut data: PartialArray<T, N>) -> Self {
+        for index in (0..data.len() / 2).rev() {
+            heap::sift_down(&mut data, index, &mut K::is_less);
+        }
+        Self {
+            data,
+            kind: PhantomData,
+        }
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> FromIterator<T> for PartialBinaryHeap<T, N, K> {
+    /// Build up a [`PartialBinaryHeap`] from an iterator with potentially less
+    /// than `N` elements.
+    ///
+    /// # Panics
+    /// Panics, if the length of the iterator is greater than the maximum length
+    /// of the heap (`N`).
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PartialArray::from_iter(iter).into()
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> Extend<T> for PartialBinaryHeap<T, N, K> {
+    /// Insert the elements of the iterator.
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more elements than there is space left.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        storage::extend_with(N - self.len(), iter, |element| self.push(element));
+    }
+}
+impl<T, const N: usize, K> IntoIterator for PartialBinaryHeap<T, N, K> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<T, N>;
+
+    /// Iterate over the elements by value in an unspecified (heap) order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+impl<'a, T, const N: usize, K> IntoIterator for &'a PartialBinaryHeap<T, N, K> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A guard for a mutable reference to the top element of a
+/// [`PartialBinaryHeap`].
+///
+/// This struct is created by the [`peek_mut`] method. The heap order is
+/// restored, when it is dropped.
+///
+/// [`peek_mut`]: PartialBinaryHeap::peek_mut
+pub struct PeekMut<'a, T: Ord, const N: usize, K: Kind> {
+    /// The (non-empty) heap.
+    heap: &'a mut PartialBinaryHeap<T, N, K>,
+}
+impl<T: Ord, const N: usize, K: Kind> PeekMut<'_, T, N, K> {
+    /// Remove the top element from the heap and return it.
+    pub fn pop(this: Self) -> T {
+        // the heap order is valid afterwards, so the sift on drop is a no-op
+        this.heap.pop().expect("the heap is never empty")
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> Drop for PeekMut<'_, T, N, K> {
+    fn drop(&mut self) {
+        heap::sift_down(&mut self.heap.data, 0, &mut K::is_less);
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> Deref for PeekMut<'_, T, N, K> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heap.data[0]
+    }
+}
+impl<T: Ord, const N: usize, K: Kind> DerefMut for PeekMut<'_, T, N, K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.heap.data[0]
+    }
+}
+impl<T: Ord + Debug, const N: usize, K: Kind> Debug for PeekMut<'_, T, N, K> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&**self).finish()
+    }
+}
@@ -0,0 +1,50 @@
+//! [`borsh`] serialization of [`PartialArray`]s.
+//!
+//! This module is only available with the `borsh` feature. A [`PartialArray`]
+//! is encoded like a `Vec<T>`, i.e. the number of filled elements (as a `u32`)
+//! followed by the filled elements. Therefore both formats are compatible with
+//! each other, as long as there are at most `N` elements.
+use crate::PartialArray;
+use borsh::io::{ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::ops::Deref;
+
+impl<T: BorshSerialize, const N: usize> BorshSerialize for PartialArray<T, N> {
+    /// Serialize the filled elements (potentially less than `N`).
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// let array: PartialArray<u8, 4> = (1..3).collect();
+    ///
+    /// assert_eq!(borsh::to_vec(&array).unwrap(), [2, 0, 0, 0, 1, 2]);
+    /// ```
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.deref().serialize(writer)
+    }
+}
+impl<T: BorshDeserialize, const N: usize> BorshDeserialize for PartialArray<T, N> {
+    /// Deserialize a [`PartialArray`] with up to `N` elements.
+    ///
+    /// An input with more than `N` elements is rejected with an error of kind
+    /// [`InvalidData`](ErrorKind::InvalidData) before reading any element.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// let array: PartialArray<u8, 4> = borsh::from_slice(&[2, 0, 0, 0, 1, 2]).unwrap();
+    /// assert_eq!(array, [1, 2]);
+    ///
+    /// let too_long = borsh::from_slice::<PartialArray<u8, 1>>(&[2, 0, 0, 0, 1, 2]);
+    /// assert!(too_long.is_err());
+    /// ```
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let len = u32::deserialize_reader(reader)? as usize;
+        if len > N {
+            // construct the error from the kind only, as this does not allocate
+            return Err(ErrorKind::InvalidData.into());
+        }
+
+        (0..len).map(|_| T::deserialize_reader(reader)).collect()
+    }
+}
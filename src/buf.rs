@@ -0,0 +1,210 @@
+//! Partially filling externally provided storage.
+//!
+//! This module provides the [`PartialBuf`] type, which behaves like a
+//! [`PartialArray`], but does not own its storage. Instead it manages the
+//! initialized prefix of a borrowed `&mut [MaybeUninit<T>]`, e.g. a DMA buffer
+//! or a region placed by the linker:
+//! ```
+//! # use core::mem::MaybeUninit;
+//! use partial_array::buf::PartialBuf;
+//!
+//! let mut storage = [MaybeUninit::<u16>::uninit(); 8];
+//! let mut buf = PartialBuf::new(&mut storage);
+//! buf.extend((1..4).map(|x| x * 10));
+//! assert_eq!(buf, [10, 20, 30]);
+//!
+//! let initialized: &mut [u16] = buf.into_initialized();
+//! assert_eq!(initialized, [10, 20, 30]);
+//! ```
+//!
+//! [`PartialArray`]: crate::PartialArray
+use crate::{storage, CapacityError};
+use core::fmt::{self, Debug, Formatter};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+
+/// A potentially partially filled, borrowed buffer.
+///
+/// This is the borrowed counterpart of a [`PartialArray`]: the capacity is the
+/// length of the borrowed storage and the filled elements are always a prefix
+/// of it. The filled elements are dropped, when the [`PartialBuf`] is dropped,
+/// unless they are handed out via [`into_initialized`].
+///
+/// Like the [`PartialArray`] it [deref]s to a slice of the filled elements.
+///
+/// [`PartialArray`]: crate::PartialArray
+/// [`into_initialized`]: PartialBuf::into_initialized
+/// [deref]: core::ops::Deref::deref
+pub struct PartialBuf<'a, T> {
+    /// The number of filled entries inside the buffer.
+    ///
+    /// Each item in `0..filled` must be initialized. Others may or may not.
+    /// This must never be greater than the length of `buf`.
+    filled: usize,
+    /// The borrowed storage for the items.
+    buf: &'a mut [MaybeUninit<T>],
+}
+impl<'a, T> PartialBuf<'a, T> {
+    /// Create an empty [`PartialBuf`] using the given storage.
+    ///
+    /// The previous content of the storage is ignored (and not dropped).
+    pub fn new(buf: &'a mut [MaybeUninit<T>]) -> Self {
+        Self { filled: 0, buf }
+    }
+
+    /// Fill the given storage with the elements of an iterator.
+    ///
+    /// This is the counterpart of collecting into a [`PartialArray`], which
+    /// needs the storage in addition to the iterator. The previous content of
+    /// the storage is ignored (and not dropped).
+    ///
+    /// # Example
+    /// ```
+    /// # use core::mem::MaybeUninit;
+    /// # use partial_array::buf::PartialBuf;
+    /// let mut storage = [MaybeUninit::uninit(); 8];
+    /// let buf = PartialBuf::from_iter_in(&mut storage, "abc".bytes());
+    ///
+    /// assert_eq!(buf, *b"abc");
+    /// ```
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more elements than the storage can hold.
+    ///
+    /// [`PartialArray`]: crate::PartialArray
+    pub fn from_iter_in<I: IntoIterator<Item = T>>(buf: &'a mut [MaybeUninit<T>], iter: I) -> Self {
+        let mut this = Self::new(buf);
+        this.extend(iter);
+        this
+    }
+
+    /// The maximum number of elements (the length of the storage).
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Check, whether no more elements can be added.
+    pub fn is_full(&self) -> bool {
+        self.filled == self.capacity()
+    }
+
+    /// Append an element to the back.
+    ///
+    /// # Panics
+    /// Panics, if the buffer is already full.
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            panic!("PartialBuf is full (capacity {})", self.capacity());
+        }
+    }
+
+    /// Append an element to the back or return it, if the buffer is full.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::mem::MaybeUninit;
+    /// # use partial_array::buf::PartialBuf;
+    /// let mut storage = [MaybeUninit::uninit(); 1];
+    /// let mut buf = PartialBuf::new(&mut storage);
+    ///
+    /// assert!(buf.try_push(1).is_ok());
+    /// assert_eq!(buf.try_push(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        storage::try_push(&mut self.filled, self.buf, value)
+    }
+
+    /// Remove the last element and return it or [`None`], if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::pop(&mut self.filled, self.buf) }
+    }
+
+    /// Shorten the buffer to the first `len` elements and drop the rest.
+    ///
+    /// This has no effect, if the buffer has `len` or less elements.
+    pub fn truncate(&mut self, len: usize) {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::truncate(&mut self.filled, self.buf, len) }
+    }
+
+    /// Remove and drop all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Hand out the filled elements for the whole lifetime of the storage.
+    ///
+    /// The elements are not dropped anymore, i.e. they are leaked, unless the
+    /// caller drops them (e.g. via [`core::ptr::drop_in_place`]).
+    ///
+    /// # Example
+    /// ```
+    /// # use core::mem::MaybeUninit;
+    /// # use partial_array::buf::PartialBuf;
+    /// let mut storage = [MaybeUninit::uninit(); 4];
+    /// let mut buf = PartialBuf::new(&mut storage);
+    /// buf.push(42);
+    ///
+    /// let values = buf.into_initialized();
+    /// values[0] += 1;
+    /// assert_eq!(values, [43]);
+    /// ```
+    pub fn into_initialized(self) -> &'a mut [T] {
+        let mut this = ManuallyDrop::new(self);
+        let filled = this.filled;
+        let buf = mem::take(&mut this.buf);
+        // SAFETY: the invariant is, that `0..self.filled` is initialized. As
+        // `self` is not dropped, the elements are not dropped either.
+        unsafe { storage::initialized_mut(buf, filled) }
+    }
+}
+impl<T> Drop for PartialBuf<'_, T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+impl<T> Deref for PartialBuf<'_, T> {
+    type Target = [T];
+
+    /// Dereference to the slice of filled elements.
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::initialized(self.buf, self.filled) }
+    }
+}
+impl<T> DerefMut for PartialBuf<'_, T> {
+    /// Dereference to the slice of filled elements.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::initialized_mut(self.buf, self.filled) }
+    }
+}
+impl<T: Debug> Debug for PartialBuf<'_, T> {
+    /// Debug-format the slice of filled elements.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+impl<T: PartialEq> PartialEq<PartialBuf<'_, T>> for PartialBuf<'_, T> {
+    /// Compare the filled elements of [`PartialBuf`]s.
+    fn eq(&self, other: &PartialBuf<'_, T>) -> bool {
+        self.deref() == other.deref()
+    }
+}
+impl<T: PartialEq, const M: usize> PartialEq<[T; M]> for PartialBuf<'_, T> {
+    /// Compare a [`PartialBuf`] with a normal array.
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.deref() == &other[..]
+    }
+}
+impl<T: Eq> Eq for PartialBuf<'_, T> {}
+impl<T> Extend<T> for PartialBuf<'_, T> {
+    /// Append the elements of the iterator.
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more elements than there is space left.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        storage::extend(&mut self.filled, self.buf, iter);
+    }
+}
@@ -0,0 +1,106 @@
+//! Sharing [`PartialArray`]s between interrupts and the main loop.
+//!
+//! This module is only available with the `critical-section` feature. It
+//! provides the [`StaticPartialArray`], which can be declared as a `static` and
+//! accessed from interrupt handlers and the main loop alike. Each access is done
+//! inside a critical section via [`critical_section::with`], so the application
+//! (or its HAL) has to provide a [critical section implementation].
+//! ```
+//! # use partial_array::critical_section::StaticPartialArray;
+//! static EVENTS: StaticPartialArray<u8, 16> = StaticPartialArray::new();
+//!
+//! // in the interrupt handler
+//! EVENTS.push(42).ok();
+//!
+//! // in the main loop
+//! for event in EVENTS.drain() {
+//!     assert_eq!(event, 42);
+//! }
+//! ```
+//!
+//! [critical section implementation]: https://docs.rs/critical-section/1/critical_section/#usage-in-no-std-binaries
+use crate::{iter::IntoIter, CapacityError, PartialArray};
+use core::cell::RefCell;
+use core::fmt::{self, Debug, Formatter};
+use core::mem;
+use critical_section::Mutex;
+
+/// A [`PartialArray`], that can be shared via a `static`.
+///
+/// All accesses lock a critical section, so they should be kept short. Nested
+/// accesses (e.g. calling [`push`] from within [`with`]) panic.
+///
+/// [`push`]: StaticPartialArray::push
+/// [`with`]: StaticPartialArray::with
+pub struct StaticPartialArray<T, const N: usize>(Mutex<RefCell<PartialArray<T, N>>>);
+impl<T, const N: usize> StaticPartialArray<T, N> {
+    /// Create an empty [`StaticPartialArray`].
+    pub const fn new() -> Self {
+        Self(Mutex::new(RefCell::new(PartialArray::new())))
+    }
+
+    /// Append an element or return it, if the array is full.
+    ///
+    /// This never panics because of a full array, which makes it suitable for
+    /// interrupt handlers.
+    pub fn push(&self, value: T) -> Result<(), CapacityError<T>> {
+        self.with(|array| array.as_partial_slice_mut().try_push(value))
+    }
+
+    /// Remove all elements and return an iterator over them.
+    ///
+    /// The elements are moved out inside a critical section, but the returned
+    /// iterator does not hold it, so it can be consumed at leisure.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::critical_section::StaticPartialArray;
+    /// static EVENTS: StaticPartialArray<u8, 4> = StaticPartialArray::new();
+    /// EVENTS.push(1).unwrap();
+    /// EVENTS.push(2).unwrap();
+    ///
+    /// assert!(EVENTS.drain().eq([1, 2].iter().copied()));
+    /// assert_eq!(EVENTS.drain().len(), 0);
+    /// ```
+    pub fn drain(&self) -> IntoIter<T, N> {
+        self.with(mem::take).into_iter()
+    }
+
+    /// Access the array mutably inside a critical section.
+    ///
+    /// # Panics
+    /// Panics, if called from within another access to the same array.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::critical_section::StaticPartialArray;
+    /// static EVENTS: StaticPartialArray<u8, 4> = StaticPartialArray::new();
+    /// EVENTS.push(1).unwrap();
+    ///
+    /// let len = EVENTS.with(|events| {
+    ///     events[0] = 42;
+    ///     events.len()
+    /// });
+    /// assert_eq!(len, 1);
+    /// ```
+    pub fn with<R>(&self, f: impl FnOnce(&mut PartialArray<T, N>) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.0.borrow_ref_mut(cs)))
+    }
+}
+impl<T, const N: usize> Default for StaticPartialArray<T, N> {
+    /// Initialize an empty [`StaticPartialArray`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone + Debug, const N: usize> Debug for StaticPartialArray<T, N> {
+    /// Debug-format a copy of the filled elements.
+    ///
+    /// Only cloning the elements happens inside a critical section, so the
+    /// formatting (and writing to the output) does not run with interrupts
+    /// disabled.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let array = self.with(|array| array.clone());
+        <[T] as Debug>::fmt(&array, f)
+    }
+}
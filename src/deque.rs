@@ -0,0 +1,494 @@
+//! A fixed-capacity double-ended queue.
+//!
+//! This module provides the [`PartialDeque`], which is a ring buffer for up to
+//! `N` elements. Like the [`PartialArray`] it stores its elements inline, but
+//! elements can be added and removed at both ends efficiently:
+//! ```
+//! # use partial_array::deque::PartialDeque;
+//! let mut deque = PartialDeque::<u8, 4>::new();
+//! deque.push_back(2);
+//! deque.push_back(3);
+//! deque.push_front(1);
+//! assert_eq!(deque, [1, 2, 3]);
+//!
+//! assert_eq!(deque.pop_front(), Some(1));
+//! assert_eq!(deque.pop_back(), Some(3));
+//! ```
+//! As the storage is bounded, the `*_overwrite` push methods can be used to
+//! evict the oldest element, if the deque is full, e.g. to keep a history of
+//! the latest samples:
+//! ```
+//! # use partial_array::deque::PartialDeque;
+//! let mut history = PartialDeque::<u8, 3>::new();
+//! for sample in 1..=5 {
+//!     history.push_back_overwrite(sample);
+//! }
+//! assert_eq!(history, [3, 4, 5]);
+//! ```
+use crate::{storage, CapacityError, PartialArray};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{Chain, FromIterator};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::{ptr, slice};
+
+/// A double-ended queue (ring buffer) with a capacity of `N` elements.
+///
+/// The elements are stored inline, potentially wrapping around the end of the
+/// storage. Therefore the elements are not necessarily contiguous, see
+/// [`as_slices`] and [`make_contiguous`].
+///
+/// [`as_slices`]: PartialDeque::as_slices
+/// [`make_contiguous`]: PartialDeque::make_contiguous
+pub struct PartialDeque<T, const N: usize> {
+    /// The index of the front element inside the storage.
+    ///
+    /// This is always less than `N` (or zero for `N == 0`).
+    head: usize,
+    /// The number of elements.
+    ///
+    /// The elements at the indices `head..head + len` (modulo `N`) must be
+    /// initialized. This must never be greater than `N`.
+    len: usize,
+    /// The storage for the items.
+    buffer: [MaybeUninit<T>; N],
+}
+impl<T, const N: usize> PartialDeque<T, N> {
+    /// Required for `MaybeUninit::uninit()` in array initializers
+    const UNINIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    /// Create an empty [`PartialDeque`].
+    pub const fn new() -> Self {
+        Self {
+            head: 0,
+            len: 0,
+            buffer: [Self::UNINIT; N],
+        }
+    }
+
+    /// The number of elements.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check, whether there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check, whether no more elements can be added.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// The maximum number of elements (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Convert a logical index into an index into the storage.
+    ///
+    /// Must only be called for `N > 0`.
+    fn physical(&self, index: usize) -> usize {
+        (self.head + index) % N
+    }
+
+    /// Append an element to the back.
+    ///
+    /// # Panics
+    /// Panics, if the deque is already full.
+    pub fn push_back(&mut self, value: T) {
+        if self.try_push_back(value).is_err() {
+            panic!("PartialDeque is full (capacity {})", N);
+        }
+    }
+
+    /// Prepend an element to the front.
+    ///
+    /// # Panics
+    /// Panics, if the deque is already full.
+    pub fn push_front(&mut self, value: T) {
+        if self.try_push_front(value).is_err() {
+            panic!("PartialDeque is full (capacity {})", N);
+        }
+    }
+
+    /// Append an element to the back or return it, if the deque is full.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(value));
+        }
+
+        let index = self.physical(self.len);
+        self.buffer[index] = MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Prepend an element to the front or return it, if the deque is full.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(value));
+        }
+
+        self.head = self.physical(N - 1);
+        self.buffer[self.head] = MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Append an element to the back, evicting the front one, if full.
+    ///
+    /// The evicted (oldest) element is returned. For a capacity of zero, the
+    /// given element itself is returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::deque::PartialDeque;
+    /// let mut deque = PartialDeque::<u8, 2>::new();
+    /// assert_eq!(deque.push_back_overwrite(1), None);
+    /// assert_eq!(deque.push_back_overwrite(2), None);
+    /// assert_eq!(deque.push_back_overwrite(3), Some(1));
+    /// assert_eq!(deque, [2, 3]);
+    /// ```
+    pub fn push_back_overwrite(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            self.pop_front()
+        } else {
+            None
+        };
+        match self.try_push_back(value) {
+            Ok(()) => evicted,
+            Err(error) => Some(error.element()),
+        }
+    }
+
+    /// Prepend an element to the front, evicting the back one, if full.
+    ///
+    /// The evicted element is returned. For a capacity of zero, the given
+    /// element itself is returned.
+    pub fn push_front_overwrite(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            self.pop_back()
+        } else {
+            None
+        };
+        match self.try_push_front(value) {
+            Ok(()) => evicted,
+            Err(error) => Some(error.element()),
+        }
+    }
+
+    /// Remove the front element and return it or [`None`], if it is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = mem::replace(&mut self.buffer[self.head], Self::UNINIT);
+        self.head = self.physical(1);
+        self.len -= 1;
+        // SAFETY: the entry was the front element, so it is initialized. It is
+        // not part of the elements anymore, so it is not read or dropped again.
+        Some(unsafe { value.assume_init() })
+    }
+
+    /// Remove the back element and return it or [`None`], if it is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.len -= 1;
+        let index = self.physical(self.len);
+        let value = mem::replace(&mut self.buffer[index], Self::UNINIT);
+        // SAFETY: the entry was the back element, so it is initialized. It is
+        // not part of the elements anymore, so it is not read or dropped again.
+        Some(unsafe { value.assume_init() })
+    }
+
+    /// Get a reference to the element at `index` (counted from the front).
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let entry = &self.buffer[self.physical(index)];
+        // SAFETY: the index is in bounds, so the entry is initialized.
+        Some(unsafe { &*entry.as_ptr() })
+    }
+
+    /// Get a mutable reference to the element at `index` (counted from the
+    /// front).
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let entry = &mut self.buffer[self.physical(index)];
+        // SAFETY: the index is in bounds, so the entry is initialized.
+        Some(unsafe { &mut *entry.as_mut_ptr() })
+    }
+
+    /// Get a reference to the front element.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Get a reference to the back element.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// Get a mutable reference to the front element.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Get a mutable reference to the back element.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.checked_sub(1)?)
+    }
+
+    /// The storage ranges of the front and back part of the elements.
+    fn ranges(&self) -> ((usize, usize), (usize, usize)) {
+        let end = self.head + self.len;
+        if end <= N {
+            ((self.head, end), (0, 0))
+        } else {
+            ((self.head, N), (0, end - N))
+        }
+    }
+
+    /// Return the elements as a pair of slices (in order).
+    ///
+    /// If the elements are contiguous, the second slice is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::deque::PartialDeque;
+    /// let mut deque = PartialDeque::<u8, 4>::new();
+    /// deque.push_back(2);
+    /// deque.push_front(1);
+    /// assert_eq!(deque.as_slices(), (&[1][..], &[2][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let ((a, b), (c, d)) = self.ranges();
+        let front = &self.buffer[a..b];
+        let back = &self.buffer[c..d];
+        // SAFETY: both ranges only contain elements, i.e. initialized entries.
+        // The transmute itself is safe, since `MaybeUninit` is
+        // `#[repr(transparent)]`.
+        unsafe {
+            (
+                mem::transmute::<&[MaybeUninit<T>], &[T]>(front),
+                mem::transmute::<&[MaybeUninit<T>], &[T]>(back),
+            )
+        }
+    }
+
+    /// Return the elements as a pair of mutable slices (in order).
+    ///
+    /// If the elements are contiguous, the second slice is empty.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let ((a, b), (c, d)) = self.ranges();
+        // the back part is located before the front part in the storage
+        let (back, front) = self.buffer.split_at_mut(a);
+        let front = &mut front[..b - a];
+        let back = &mut back[c..d];
+        // SAFETY: see `as_slices()`
+        unsafe {
+            (
+                mem::transmute::<&mut [MaybeUninit<T>], &mut [T]>(front),
+                mem::transmute::<&mut [MaybeUninit<T>], &mut [T]>(back),
+            )
+        }
+    }
+
+    /// Move the elements, so that they are contiguous, and return them.
+    ///
+    /// Afterwards the elements start at the beginning of the storage and
+    /// [`as_slices`] returns an empty second slice.
+    ///
+    /// [`as_slices`]: PartialDeque::as_slices
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        // rotating the uninitialized entries around does not hurt, as they are
+        // moved bitwise only
+        self.buffer.rotate_left(self.head);
+        self.head = 0;
+        self.as_mut_slices().0
+    }
+
+    /// Rotate the elements `n` places to the left.
+    ///
+    /// Afterwards, the element at index `n` is the front element.
+    ///
+    /// # Panics
+    /// Panics, if `n` is greater than the length.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length");
+        self.make_contiguous().rotate_left(n);
+    }
+
+    /// Rotate the elements `n` places to the right.
+    ///
+    /// Afterwards, the element at index `len - n` is the front element.
+    ///
+    /// # Panics
+    /// Panics, if `n` is greater than the length.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length");
+        self.make_contiguous().rotate_right(n);
+    }
+
+    /// Iterate over the elements from front to back.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+
+    /// Iterate mutably over the elements from front to back.
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back)
+    }
+
+    /// Remove and drop all elements.
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+    }
+}
+impl<T, const N: usize> Drop for PartialDeque<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+impl<T, const N: usize> Default for PartialDeque<T, N> {
+    /// Initialize an empty [`PartialDeque`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone, const N: usize> Clone for PartialDeque<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+impl<T: Debug, const N: usize> Debug for PartialDeque<T, N> {
+    /// Debug-format the elements from front to back.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<PartialDeque<T, M>>
+    for PartialDeque<T, N>
+{
+    /// Compare the elements of [`PartialDeque`]s (regardless of the layout).
+    fn eq(&self, other: &PartialDeque<T, M>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for PartialDeque<T, N> {
+    /// Compare a [`PartialDeque`] with a normal array.
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.len == M && self.iter().eq(other.iter())
+    }
+}
+impl<T: Eq, const N: usize> Eq for PartialDeque<T, N> {}
+impl<T, const N: usize> Index<usize> for PartialDeque<T, N> {
+    type Output = T;
+
+    /// Access the element at `index` (counted from the front).
+    ///
+    /// # Panics
+    /// Panics, if the index is out of bounds.
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+impl<T, const N: usize> IndexMut<usize> for PartialDeque<T, N> {
+    /// Mutably access the element at `index` (counted from the front).
+    ///
+    /// # Panics
+    /// Panics, if the index is out of bounds.
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+impl<T, const N: usize> FromIterator<T> for PartialDeque<T, N> {
+    /// Build up a [`PartialDeque`] from an iterator with potentially less than
+    /// `N` elements.
+    ///
+    /// # Panics
+    /// Panics, if the length of the iterator is greater than the maximum length
+    /// of the deque (`N`).
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+impl<T, const N: usize> Extend<T> for PartialDeque<T, N> {
+    /// Append the elements of the iterator to the back.
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more elements than there is space left.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        storage::extend_with(N - self.len, iter, |element| self.push_back(element));
+    }
+}
+impl<T, const N: usize> From<PartialArray<T, N>> for PartialDeque<T, N> {
+    /// Convert a [`PartialArray`] into a [`PartialDeque`] without moving the
+    /// elements inside the storage.
+    fn from(array: PartialArray<T, N>) -> Self {
+        let array = ManuallyDrop::new(array);
+        Self {
+            head: 0,
+            len: array.filled,
+            // SAFETY: the storage is moved out of the array, which is never
+            // used or dropped again. The invariants are the same.
+            buffer: unsafe { ptr::read(&array.array) },
+        }
+    }
+}
+impl<T, const N: usize> From<PartialDeque<T, N>> for PartialArray<T, N> {
+    /// Convert a [`PartialDeque`] into a [`PartialArray`].
+    ///
+    /// The elements are made contiguous, which might move them.
+    fn from(mut deque: PartialDeque<T, N>) -> Self {
+        deque.make_contiguous();
+        let deque = ManuallyDrop::new(deque);
+        Self {
+            filled: deque.len,
+            // SAFETY: the storage is moved out of the deque, which is never
+            // used or dropped again. As the elements start at index 0, the
+            // invariants are the same.
+            array: unsafe { ptr::read(&deque.buffer) },
+        }
+    }
+}
+impl<T, const N: usize> IntoIterator for PartialDeque<T, N> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<T, N>;
+
+    /// Iterate over the elements from front to back by value.
+    fn into_iter(self) -> Self::IntoIter {
+        PartialArray::from(self).into_iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a PartialDeque<T, N> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a mut PartialDeque<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
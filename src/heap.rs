@@ -0,0 +1,54 @@
+//! Binary max-heap operations on slices.
+//!
+//! The heap order is given by an `is_less` function: no element is less than
+//! one of its children, i.e. the greatest element is at index `0`. Only safe
+//! swaps are used, so a panicking comparison leaves a valid (but potentially
+//! unordered) slice.
+
+/// Move the element at `index` up, until its parent is not less than it.
+pub(crate) fn sift_up<T>(
+    heap: &mut [T],
+    mut index: usize,
+    is_less: &mut impl FnMut(&T, &T) -> bool,
+) {
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if !is_less(&heap[parent], &heap[index]) {
+            break;
+        }
+        heap.swap(parent, index);
+        index = parent;
+    }
+}
+
+/// Move the element at `index` down, until no child is greater than it.
+pub(crate) fn sift_down<T>(
+    heap: &mut [T],
+    mut index: usize,
+    is_less: &mut impl FnMut(&T, &T) -> bool,
+) {
+    loop {
+        let left = 2 * index + 1;
+        let right = left + 1;
+        let mut greatest = index;
+        if left < heap.len() && is_less(&heap[greatest], &heap[left]) {
+            greatest = left;
+        }
+        if right < heap.len() && is_less(&heap[greatest], &heap[right]) {
+            greatest = right;
+        }
+        if greatest == index {
+            break;
+        }
+        heap.swap(index, greatest);
+        index = greatest;
+    }
+}
+
+/// Sort a heap ascendingly in-place (heap sort).
+pub(crate) fn sort<T>(heap: &mut [T], is_less: &mut impl FnMut(&T, &T) -> bool) {
+    for end in (1..heap.len()).rev() {
+        heap.swap(0, end);
+        sift_down(&mut heap[..end], 0, is_less);
+    }
+}
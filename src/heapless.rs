@@ -0,0 +1,81 @@
+//! Interoperability with [`heapless::Vec`].
+//!
+//! This module is only available with the `heapless` feature. It provides
+//! conversions between [`PartialArray`]s and [`heapless::Vec`]s in both
+//! directions, which move the elements in bulk. The conversions work for any
+//! capacities and only fail, if the elements do not fit into the target. In
+//! that case, the original collection is returned in the [`CapacityError`].
+//! Furthermore, both types can be compared with each other.
+//! ```
+//! # use core::convert::TryFrom;
+//! # use partial_array::PartialArray;
+//! let vec: heapless::Vec<u8, 16> = heapless::Vec::from_slice(&[1, 2, 3]).unwrap();
+//! let array = PartialArray::<u8, 4>::try_from(vec).unwrap();
+//! assert_eq!(array, [1, 2, 3]);
+//!
+//! let vec = heapless::Vec::<u8, 2>::try_from(array).unwrap_err().element();
+//! assert_eq!(vec, [1, 2, 3]);
+//! ```
+use crate::{CapacityError, PartialArray};
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+impl<T, const N: usize, const M: usize> TryFrom<heapless::Vec<T, M>> for PartialArray<T, N> {
+    type Error = CapacityError<heapless::Vec<T, M>>;
+
+    /// Move the elements of a [`heapless::Vec`] into a [`PartialArray`].
+    ///
+    /// This fails, if there are more than `N` elements.
+    fn try_from(mut vec: heapless::Vec<T, M>) -> Result<Self, Self::Error> {
+        if vec.len() > N {
+            return Err(CapacityError::new(vec));
+        }
+
+        let mut array = Self::default();
+        // SAFETY: the elements of the vector are initialized and there is
+        // enough space. The vector forgets about its elements afterwards, so
+        // they are moved.
+        unsafe {
+            array.append_raw(vec.as_ptr(), vec.len());
+            vec.set_len(0);
+        }
+        Ok(array)
+    }
+}
+impl<T, const N: usize, const M: usize> TryFrom<PartialArray<T, N>> for heapless::Vec<T, M> {
+    type Error = CapacityError<PartialArray<T, N>>;
+
+    /// Move the elements of a [`PartialArray`] into a [`heapless::Vec`].
+    ///
+    /// This fails, if there are more than `M` elements.
+    fn try_from(mut array: PartialArray<T, N>) -> Result<Self, Self::Error> {
+        if array.len() > M {
+            return Err(CapacityError::new(array));
+        }
+
+        let mut vec = Self::new();
+        // SAFETY: the vector is empty and has enough space for all elements.
+        // The array forgets about its elements, so they are moved.
+        unsafe {
+            let len = array.take_raw(vec.as_mut_ptr());
+            vec.set_len(len);
+        }
+        Ok(vec)
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<heapless::Vec<T, M>>
+    for PartialArray<T, N>
+{
+    /// Compare the filled elements with the elements of a [`heapless::Vec`].
+    fn eq(&self, other: &heapless::Vec<T, M>) -> bool {
+        self.deref() == other.deref()
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<PartialArray<T, M>>
+    for heapless::Vec<T, N>
+{
+    /// Compare the elements of a [`heapless::Vec`] with the filled elements.
+    fn eq(&self, other: &PartialArray<T, M>) -> bool {
+        self.deref() == other.deref()
+    }
+}
@@ -62,7 +62,7 @@ impl<T: Debug, const N: usize> Debug for IntoIter<T, N> {
         // SAFETY: the invariant is: `self.read..self.filled` is initialized, so
         // it is no UB reading those. The transmute itself is safe, since
         // `MaybeUninit` is `#[repr(transparent)]`.
-        let slice = unsafe { mem::transmute(slice) };
+        let slice = unsafe { mem::transmute::<&[MaybeUninit<T>], &[T]>(slice) };
         <[T] as Debug>::fmt(slice, f)
     }
 }
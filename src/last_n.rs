@@ -0,0 +1,119 @@
+//! Keeping the last `N` items of an iterator.
+//!
+//! This module provides the [`LastN`] collector. Unlike a [`PartialArray`],
+//! which panics, if it is filled with more than `N` elements, the [`LastN`]
+//! evicts the oldest element instead. This allows to collect e.g. the latest
+//! samples of a stream:
+//! ```
+//! # use partial_array::PartialArray;
+//! use partial_array::last_n::LastN;
+//!
+//! let samples = (1..=100).map(|x| x * 2);
+//! let latest: LastN<u32, 3> = samples.collect();
+//! assert_eq!(latest.into_partial_array(), [196, 198, 200]);
+//! ```
+use crate::deque::PartialDeque;
+use crate::PartialArray;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{Chain, FromIterator};
+use core::slice;
+
+/// A collector, that keeps the most recent `N` items.
+///
+/// Inserting an item is O(1): if there are already `N` items, the oldest one is
+/// evicted. The items are kept in chronological order (oldest first).
+#[derive(Clone, PartialEq, Eq)]
+pub struct LastN<T, const N: usize>(PartialDeque<T, N>);
+impl<T, const N: usize> LastN<T, N> {
+    /// Create an empty [`LastN`].
+    pub const fn new() -> Self {
+        Self(PartialDeque::new())
+    }
+
+    /// Insert an item and return the evicted oldest item, if any.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::last_n::LastN;
+    /// let mut last = LastN::<u8, 2>::new();
+    /// assert_eq!(last.push(1), None);
+    /// assert_eq!(last.push(2), None);
+    /// assert_eq!(last.push(3), Some(1));
+    /// ```
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.0.push_back_overwrite(value)
+    }
+
+    /// The number of kept items.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check, whether no items are kept.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the kept items in chronological order (oldest first).
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        self.0.iter()
+    }
+
+    /// Get the kept items in chronological order (oldest first).
+    pub fn into_partial_array(self) -> PartialArray<T, N> {
+        self.0.into()
+    }
+}
+impl<T, const N: usize> Default for LastN<T, N> {
+    /// Initialize an empty [`LastN`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Debug, const N: usize> Debug for LastN<T, N> {
+    /// Debug-format the kept items in chronological order.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+impl<T, const N: usize> FromIterator<T> for LastN<T, N> {
+    /// Collect the last `N` items of an iterator.
+    ///
+    /// This never panics, regardless of the length of the iterator.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+impl<T, const N: usize> Extend<T> for LastN<T, N> {
+    /// Insert all items of the iterator, evicting the oldest ones if necessary.
+    ///
+    /// This never panics, regardless of the length of the iterator.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| drop(self.push(value)));
+    }
+}
+impl<T, const N: usize> From<LastN<T, N>> for PartialArray<T, N> {
+    /// Get the kept items in chronological order (oldest first).
+    fn from(last: LastN<T, N>) -> Self {
+        last.into_partial_array()
+    }
+}
+impl<T, const N: usize> IntoIterator for LastN<T, N> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<T, N>;
+
+    /// Iterate over the kept items in chronological order by value.
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a LastN<T, N> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -75,7 +75,62 @@
 //! [macro]: crate::partial_array
 #![cfg_attr(not(test), no_std)] // allow `std` for tests
 
+#[cfg(any(
+    feature = "alloc",
+    feature = "proptest",
+    feature = "quickcheck",
+    feature = "rayon"
+))]
+extern crate alloc;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(target_has_atomic = "ptr")]
+pub mod atomic;
+pub mod binary_heap;
+#[cfg(feature = "borsh")]
+mod borsh;
+pub mod buf;
+#[cfg(feature = "critical-section")]
+pub mod critical_section;
+pub mod deque;
+mod heap;
+#[cfg(feature = "heapless")]
+mod heapless;
 pub mod iter;
+pub mod last_n;
+pub mod lru;
+pub mod map;
+#[cfg(feature = "bytemuck")]
+pub mod pod;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "parity-scale-codec")]
+mod scale;
+pub mod set;
+pub mod slab;
+pub mod slice;
+pub mod sorted;
+pub mod sparse;
+#[cfg(feature = "alloc")]
+pub mod spill;
+#[cfg(target_has_atomic = "ptr")]
+pub mod spsc;
+mod storage;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(target_has_atomic = "ptr")]
+mod sync;
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
 
 #[cfg(test)]
 mod tests;
@@ -84,8 +139,10 @@ use core::cmp::Ordering;
 use core::fmt::{self, Debug, Formatter};
 use core::hash::{Hash, Hasher};
 use core::iter::{FromIterator, IntoIterator};
-use core::mem::{self, MaybeUninit};
+use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ops::{Deref, DerefMut};
+use core::ptr;
+use slice::PartialSlice;
 
 /// A potentially partially filled array.
 ///
@@ -116,8 +173,17 @@ use core::ops::{Deref, DerefMut};
 /// See the [crate-level-documentation](crate) for more information on the
 /// intended usage.
 ///
+/// The layout is `#[repr(C)]`: the number of filled elements (a `usize`)
+/// followed by the storage for `N` elements.
+///
+/// For fuzzing and property-based testing, the `arbitrary`, `proptest` and
+/// `quickcheck` features generate random arrays. Their lengths are biased
+/// towards empty and full arrays, as those are the most interesting edge
+/// cases.
+///
 /// [deref]: core::ops::Deref::deref
 /// [collect]: Iterator::collect
+#[repr(C)]
 pub struct PartialArray<T, const N: usize> {
     /// The number of filled entries inside the array.
     ///
@@ -132,26 +198,20 @@ pub struct PartialArray<T, const N: usize> {
     array: [MaybeUninit<T>; N],
 }
 impl<T, const N: usize> Deref for PartialArray<T, N> {
-    /// A [`PartialArray<T, _>`] dereferences to a [slice of `T`][slice].
+    /// A [`PartialArray<T, _>`] dereferences to a [slice of `T`][prim@slice].
     type Target = [T];
 
     /// Dereference to the slice of filled elements (potentially less than `N`).
     fn deref(&self) -> &Self::Target {
-        let slice = &self.array[..self.filled];
-        // SAFETY: the invariant is, that `0..self.filled` is initialized, so it
-        // is no UB reading those. The transmute itself is safe, since
-        // `MaybeUninit` is `#[rpr(transparent)]`.
-        unsafe { mem::transmute(slice) }
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::initialized(&self.array, self.filled) }
     }
 }
 impl<T, const N: usize> DerefMut for PartialArray<T, N> {
     /// Dereference to the slice of filled elements (potentially less than `N`).
     fn deref_mut(&mut self) -> &mut Self::Target {
-        let slice = &mut self.array[..self.filled];
-        // SAFETY: the invariant is, that `0..self.filled` is initialized, so it
-        // is no UB reading those. The transmute itself is safe, since
-        // `MaybeUninit` is `#[rpr(transparent)]`.
-        unsafe { mem::transmute(slice) }
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::initialized_mut(&mut self.array, self.filled) }
     }
 }
 impl<T: Debug, const N: usize> Debug for PartialArray<T, N> {
@@ -197,7 +257,7 @@ impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for Partial
     /// assert_eq!(b, a);
     /// ```
     fn eq(&self, other: &[T; M]) -> bool {
-        self.len() == other.len() && self.deref() == other.deref()
+        self.len() == other.len() && self.deref() == &other[..]
     }
 }
 impl<T: PartialEq, const N: usize, const M: usize> PartialEq<PartialArray<T, M>> for [T; N] {
@@ -217,7 +277,7 @@ impl<T: PartialEq, const N: usize, const M: usize> PartialEq<PartialArray<T, M>>
     /// assert_eq!(b, a);
     /// ```
     fn eq(&self, other: &PartialArray<T, M>) -> bool {
-        self.len() == other.len() && self.deref() == other.deref()
+        self.len() == other.len() && &self[..] == other.deref()
     }
 }
 impl<T: PartialEq, const N: usize> PartialEq<&[T]> for PartialArray<T, N> {
@@ -232,7 +292,7 @@ impl<T: PartialEq, const N: usize> PartialEq<&[T]> for PartialArray<T, N> {
     /// assert_eq!(a, b);
     /// ```
     fn eq(&self, other: &&[T]) -> bool {
-        self.len() == other.len() && self.deref() == other.deref()
+        self.len() == other.len() && self.deref() == *other
     }
 }
 impl<T: PartialEq, const N: usize> PartialEq<PartialArray<T, N>> for &[T] {
@@ -252,17 +312,14 @@ impl<T: PartialEq, const N: usize> PartialEq<PartialArray<T, N>> for &[T] {
     /// assert_eq!(b, a);
     /// ```
     fn eq(&self, other: &PartialArray<T, N>) -> bool {
-        self.len() == other.len() && self.deref() == other.deref()
+        self.len() == other.len() && *self == other.deref()
     }
 }
 impl<T: Eq, const N: usize> Eq for PartialArray<T, N> {}
 impl<T, const N: usize> Default for PartialArray<T, N> {
     /// Initialize an empty [`PartialArray`].
     fn default() -> Self {
-        Self {
-            array: [Self::UNINIT; N],
-            filled: 0,
-        }
+        Self::new()
     }
 }
 impl<T: Hash, const N: usize> Hash for PartialArray<T, N> {
@@ -320,19 +377,270 @@ impl<T: Clone, const N: usize> Clone for PartialArray<T, N> {
 }
 impl<T, const N: usize> Drop for PartialArray<T, N> {
     fn drop(&mut self) {
-        (0..self.filled)
-            .map(|i| mem::replace(&mut self.array[i], Self::UNINIT))
-            .map(|entry| {
-                // SAFETY: we only iterate over the filled entries, so we can be
-                // sure, that this is initialized.
-                unsafe { entry.assume_init() }
-            })
-            .for_each(drop);
+        self.clear();
     }
 }
 impl<T, const N: usize> PartialArray<T, N> {
     /// Required for `MaybeUninit::uninit()` in array initializers
     const UNINIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    /// Create an empty [`PartialArray`].
+    ///
+    /// This is a `const fn`, so it can be used to initialize `static`s and
+    /// `const`s without lazy initialization.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// static EMPTY: PartialArray<u32, 16> = PartialArray::new();
+    /// assert!(EMPTY.is_empty());
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            array: [Self::UNINIT; N],
+            filled: 0,
+        }
+    }
+
+    /// Create a full [`PartialArray`] from an array (usable in `const`s).
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// const ARRAY: PartialArray<u8, 3> = PartialArray::from_array([1, 2, 3]);
+    /// assert_eq!(ARRAY.len(), 3);
+    /// ```
+    pub const fn from_array(array: [T; N]) -> Self {
+        Self::from_prefix(array)
+    }
+
+    /// Create a [`PartialArray`] filled with the `M` elements of an array.
+    ///
+    /// This is a `const fn`, which allows to build tables of partial arrays
+    /// with differing lengths at compile time.
+    ///
+    /// # Panics
+    /// Panics, if the array has more elements than the capacity (`N`). In a
+    /// `const` context this is a compile time error.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// const TABLE: [PartialArray<u8, 4>; 2] = [
+    ///     PartialArray::from_prefix([1]),
+    ///     PartialArray::from_prefix([1, 2, 3]),
+    /// ];
+    /// assert_eq!(TABLE[0], [1]);
+    /// assert_eq!(TABLE[1], [1, 2, 3]);
+    /// ```
+    pub const fn from_prefix<const M: usize>(array: [T; M]) -> Self {
+        assert!(M <= N, "array has more elements than the capacity");
+
+        let array = ManuallyDrop::new(array);
+        let src = &array as *const ManuallyDrop<[T; M]> as *const T;
+        let mut result = Self::new();
+        while result.filled < M {
+            // SAFETY: `src` points to `M` initialized elements, each of which is
+            // read exactly once. The source is not dropped, so the elements are
+            // moved into the result.
+            let value = unsafe { ptr::read(src.add(result.filled)) };
+            result.array[result.filled] = MaybeUninit::new(value);
+            result.filled += 1;
+        }
+        result
+    }
+
+    /// The number of filled elements.
+    pub const fn len(&self) -> usize {
+        self.filled
+    }
+
+    /// Check, whether there are no filled elements.
+    pub const fn is_empty(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// The maximum number of elements (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Remove the last element and return it or [`None`], if it is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::partial_array;
+    /// let mut array = partial_array![1, 2, 3];
+    ///
+    /// assert_eq!(array.pop(), Some(3));
+    /// assert_eq!(array, [1, 2]);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::pop(&mut self.filled, &mut self.array) }
+    }
+
+    /// Shorten the array to the first `len` elements and drop the rest.
+    ///
+    /// This has no effect, if the array has `len` or less elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::partial_array;
+    /// let mut array = partial_array![1, 2, 3, 4];
+    ///
+    /// array.truncate(2);
+    /// assert_eq!(array, [1, 2]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::truncate(&mut self.filled, &mut self.array, len) }
+    }
+
+    /// Move `len` elements from `src` to the end of the array.
+    ///
+    /// # Safety
+    /// `src` must be valid for reads of `len` initialized elements, which are
+    /// moved into the array, i.e. they must not be used or dropped afterwards.
+    /// There must be space for at least `len` additional elements.
+    #[cfg(any(feature = "arrayvec", feature = "heapless"))]
+    pub(crate) unsafe fn append_raw(&mut self, src: *const T, len: usize) {
+        debug_assert!(len <= N - self.filled);
+        let dst = self.array.as_mut_ptr().add(self.filled).cast::<T>();
+        ptr::copy_nonoverlapping(src, dst, len);
+        self.filled += len;
+    }
+
+    /// Move all elements to `dst` and return their number.
+    ///
+    /// The array is empty afterwards.
+    ///
+    /// # Safety
+    /// `dst` must be valid for writes of all filled elements. The
+    /// caller takes ownership of the moved elements.
+    #[cfg(any(feature = "arrayvec", feature = "heapless"))]
+    pub(crate) unsafe fn take_raw(&mut self, dst: *mut T) -> usize {
+        let len = self.filled;
+        self.filled = 0;
+        ptr::copy_nonoverlapping(self.array.as_ptr().cast::<T>(), dst, len);
+        len
+    }
+
+    /// Remove and drop all elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::partial_array;
+    /// let mut array = partial_array![1, 2, 3, 4];
+    ///
+    /// array.clear();
+    /// assert!(array.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// View the array as a [`PartialSlice`], which is independent of `N`.
+    pub fn as_partial_slice(&self) -> &PartialSlice<T> {
+        PartialSlice::new(self)
+    }
+
+    /// Mutably view the array as a [`PartialSlice`], which is independent of
+    /// `N`.
+    ///
+    /// This allows non-generic code to add and remove elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// # use partial_array::slice::PartialSlice;
+    /// fn append_answer(slice: &mut PartialSlice<u8>) {
+    ///     slice.push(42);
+    /// }
+    ///
+    /// let mut array = PartialArray::<u8, 4>::default();
+    /// append_answer(array.as_partial_slice_mut());
+    /// assert_eq!(array, [42]);
+    /// ```
+    pub fn as_partial_slice_mut(&mut self) -> &mut PartialSlice<T> {
+        PartialSlice::new_mut(self)
+    }
+
+    /// Select the (up to) `N` smallest elements of an iterator.
+    ///
+    /// The result is sorted ascendingly. This does not allocate and takes
+    /// O(n log N) time for an iterator with `n` elements. Use
+    /// [`core::cmp::Reverse`] in [`smallest_k_by_key`] to select the largest
+    /// elements instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// let smallest = PartialArray::<_, 3>::smallest_k([5, 1, 4, 2, 3]);
+    /// assert_eq!(smallest, [1, 2, 3]);
+    /// ```
+    ///
+    /// [`smallest_k_by_key`]: PartialArray::smallest_k_by_key
+    pub fn smallest_k<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Ord,
+    {
+        Self::smallest_k_by(iter, T::cmp)
+    }
+
+    /// Select the (up to) `N` smallest elements of an iterator with respect
+    /// to a comparison function.
+    ///
+    /// The result is sorted ascendingly with respect to the comparison
+    /// function. The selection and sorting is not stable, i.e. the order of
+    /// equal elements is unspecified.
+    pub fn smallest_k_by<I, F>(iter: I, mut compare: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut is_less = |a: &T, b: &T| compare(a, b) == Ordering::Less;
+
+        // a max-heap of the smallest elements so far: the root is the greatest
+        // one, which is replaced, if a smaller element is encountered
+        let mut heap = Self::new();
+        for element in iter {
+            if !heap.as_partial_slice().is_full() {
+                heap.as_partial_slice_mut().push(element);
+                let index = heap.filled - 1;
+                heap::sift_up(&mut heap, index, &mut is_less);
+            } else if N > 0 && is_less(&element, &heap[0]) {
+                heap[0] = element;
+                heap::sift_down(&mut heap, 0, &mut is_less);
+            }
+        }
+
+        heap::sort(&mut heap, &mut is_less);
+        heap
+    }
+
+    /// Select the (up to) `N` elements of an iterator with the smallest keys.
+    ///
+    /// The result is sorted ascendingly by the key. The key function is called
+    /// multiple times per element.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// let points = [(3, 4), (0, 1), (5, 5), (1, 1)];
+    /// let distance = |&(x, y): &(i32, i32)| x * x + y * y;
+    /// let closest = PartialArray::<_, 2>::smallest_k_by_key(points.iter().copied(), distance);
+    /// assert_eq!(closest, [(0, 1), (1, 1)]);
+    /// ```
+    pub fn smallest_k_by_key<I, K, F>(iter: I, mut key: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::smallest_k_by(iter, |a, b| key(a).cmp(&key(b)))
+    }
 }
 impl<T, const N: usize> FromIterator<T> for PartialArray<T, N> {
     /// Build up a [`PartialArray`] from an iterator with potentially less than
@@ -356,6 +664,8 @@ impl<T, const N: usize> FromIterator<T> for PartialArray<T, N> {
     /// assert_eq!(distinct_channels.len(), 4);
     /// assert_eq!(distinct_channels, [8, 12, 13, 255]);
     /// ```
+    /// For distinct values, collecting into a [`PartialSet`](set::PartialSet)
+    /// is simpler, as it removes duplicates itself.
     ///
     /// # Panics
     /// Panics, if the length of the iterator os greater than the maximum length
@@ -368,17 +678,7 @@ impl<T, const N: usize> FromIterator<T> for PartialArray<T, N> {
 }
 impl<T, const N: usize> Extend<T> for PartialArray<T, N> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        let remaining = (self.filled..N).len();
-        let mut iter = iter.into_iter();
-
-        iter.by_ref().take(remaining).for_each(|element| {
-            self.array[self.filled] = MaybeUninit::new(element);
-            self.filled += 1;
-        });
-
-        // check, that there are no more elements left
-        let remaining = iter.count();
-        assert_eq!(remaining, 0, "Iterator has {} elements to much", remaining);
+        storage::extend(&mut self.filled, &mut self.array, iter);
     }
 }
 impl<T, const N: usize> IntoIterator for PartialArray<T, N> {
@@ -392,8 +692,42 @@ impl<T, const N: usize> IntoIterator for PartialArray<T, N> {
 // TODO: generalize to From<[T; M]> for PartialArray<T, N> where M <= N
 impl<T, const N: usize> From<[T; N]> for PartialArray<T, N> {
     fn from(array: [T; N]) -> Self {
-        // TODO: is there a more performant way? Maybe with unsafe
-        core::array::IntoIter::new(array).collect()
+        Self::from_array(array)
+    }
+}
+
+/// The error returned, if there is not enough capacity for an operation.
+///
+/// The error contains the value, that could not be stored (e.g. the element or
+/// the collection to convert), so that it is not lost.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+impl<T> CapacityError<T> {
+    /// Create a new [`CapacityError`] from the value, that could not be stored.
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    /// Extract the value, that could not be stored.
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Convert into a [`CapacityError`] without the value.
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+impl<T> Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "CapacityError: insufficient capacity")
+    }
+}
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "insufficient capacity")
     }
 }
 
@@ -407,12 +741,19 @@ impl<T, const N: usize> From<[T; N]> for PartialArray<T, N> {
 /// assert_eq!(partial_array![17, 12, 2, ], PartialArray::from([17, 12, 2]));
 /// assert_eq!(partial_array![42; 5], PartialArray::from([42; 5]));
 /// ```
+/// The macro can be used in `const` contexts, too:
+/// ```
+/// use partial_array::{partial_array, PartialArray};
+///
+/// const ARRAY: PartialArray<u8, 3> = partial_array![1, 2, 3];
+/// static ZEROS: PartialArray<u8, 8> = partial_array![0; 8];
+/// ```
 #[macro_export]
 macro_rules! partial_array {
     ($($element:expr),*$(,)?) => {
-        $crate::PartialArray::from([$($element),*])
+        $crate::PartialArray::from_array([$($element),*])
     };
     ($element:expr; $n: literal) => {
-        $crate::PartialArray::from([$element; $n])
+        $crate::PartialArray::from_array([$element; $n])
     };
 }
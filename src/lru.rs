@@ -0,0 +1,353 @@
+//! A fixed-capacity least-recently-used cache.
+//!
+//! This module provides the [`PartialLru`], which keeps up to `N` entries in a
+//! [`PartialArray`]. The entries are linked in the order of their last use, so
+//! inserting into a full cache evicts the least recently used entry instead of
+//! allocating:
+//! ```
+//! use partial_array::lru::PartialLru;
+//!
+//! let mut calibrations = PartialLru::<u8, f32, 2>::new();
+//! calibrations.put(1, 0.5);
+//! calibrations.put(2, 1.5);
+//!
+//! // using the first sensor makes the second one the least recently used
+//! assert_eq!(calibrations.get(&1), Some(&0.5));
+//! assert_eq!(calibrations.put(3, 2.5), Some((2, 1.5)));
+//! assert_eq!(calibrations.peek(&2), None);
+//! ```
+use crate::PartialArray;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::mem;
+
+/// The end of the recency list.
+const END: usize = usize::MAX;
+
+/// An entry of a [`PartialLru`] together with its links in the recency list.
+#[derive(Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// The index of the next more recently used entry or `END`.
+    prev: usize,
+    /// The index of the next less recently used entry or `END`.
+    next: usize,
+}
+
+/// A cache of up to `N` entries, which evicts the least recently used entry.
+///
+/// Keys are looked up by a linear search, so operations take O(N) time. This
+/// is intended for small caches, for which this is faster than hashing. The
+/// entries are stored in a [`PartialArray`] and doubly linked in the order of
+/// their last use (via indices), so updating the recency takes O(1) time.
+#[derive(Clone)]
+pub struct PartialLru<K, V, const N: usize> {
+    /// The entries in an unspecified order.
+    entries: PartialArray<Node<K, V>, N>,
+    /// The index of the most recently used entry or `END`, if it is empty.
+    head: usize,
+    /// The index of the least recently used entry or `END`, if it is empty.
+    tail: usize,
+}
+impl<K, V, const N: usize> PartialLru<K, V, N> {
+    /// Create an empty [`PartialLru`].
+    pub const fn new() -> Self {
+        Self {
+            entries: PartialArray::new(),
+            head: END,
+            tail: END,
+        }
+    }
+
+    /// The number of entries.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check, whether there are no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The maximum number of entries (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check, whether inserting a new key evicts an entry.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Remove and drop all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.head = END;
+        self.tail = END;
+    }
+
+    /// Iterate over the entries from the most to the least recently used one.
+    ///
+    /// This does not change the recency of the entries.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: &self.entries,
+            front: self.head,
+            back: self.tail,
+            remaining: self.len(),
+        }
+    }
+
+    /// Remove the least recently used entry and return it or [`None`], if it
+    /// is empty.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        if self.tail == END {
+            return None;
+        }
+        Some(self.remove_index(self.tail))
+    }
+
+    /// Remove an entry from the recency list.
+    fn unlink(&mut self, index: usize) {
+        let Node { prev, next, .. } = self.entries[index];
+        match prev {
+            END => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            END => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    /// Insert an (unlinked) entry as the most recently used one.
+    fn link_front(&mut self, index: usize) {
+        self.entries[index].prev = END;
+        self.entries[index].next = self.head;
+        match self.head {
+            END => self.tail = index,
+            head => self.entries[head].prev = index,
+        }
+        self.head = index;
+    }
+
+    /// Make an entry the most recently used one.
+    fn promote(&mut self, index: usize) {
+        if self.head != index {
+            self.unlink(index);
+            self.link_front(index);
+        }
+    }
+
+    /// Remove the entry at `index` of the storage and return it.
+    fn remove_index(&mut self, index: usize) -> (K, V) {
+        self.unlink(index);
+
+        // move the last entry into the gap, so that the storage stays filled
+        let last = self.len() - 1;
+        if index != last {
+            self.entries.swap(index, last);
+            let Node { prev, next, .. } = self.entries[index];
+            match prev {
+                END => self.head = index,
+                prev => self.entries[prev].next = index,
+            }
+            match next {
+                END => self.tail = index,
+                next => self.entries[next].prev = index,
+            }
+        }
+
+        let node = self.entries.pop().expect("the index is in bounds");
+        (node.key, node.value)
+    }
+}
+impl<K: Eq, V, const N: usize> PartialLru<K, V, N> {
+    /// Search the storage index of a key.
+    fn search<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries
+            .iter()
+            .position(|node| node.key.borrow() == key)
+    }
+
+    /// Get a reference to the value of a key and mark it as the most recently
+    /// used entry or return [`None`], if the key is missing.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Get a mutable reference to the value of a key and mark it as the most
+    /// recently used entry or return [`None`], if the key is missing.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.search(key)?;
+        self.promote(index);
+        Some(&mut self.entries[index].value)
+    }
+
+    /// Get a reference to the value of a key without changing its recency or
+    /// return [`None`], if the key is missing.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.search(key)?;
+        Some(&self.entries[index].value)
+    }
+
+    /// Check, whether there is a value for the key without changing its
+    /// recency.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.search(key).is_some()
+    }
+
+    /// Insert an entry as the most recently used one and return the entry,
+    /// that was removed from the cache (if any).
+    ///
+    /// If the key is already present, the previous entry (with the stored key)
+    /// is replaced and returned. Otherwise, if the cache is full, the least
+    /// recently used entry is evicted and returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::lru::PartialLru;
+    /// let mut cache = PartialLru::<&str, u32, 2>::new();
+    ///
+    /// assert_eq!(cache.put("a", 1), None);
+    /// assert_eq!(cache.put("b", 2), None);
+    /// assert_eq!(cache.put("a", 3), Some(("a", 1)));
+    /// assert_eq!(cache.put("c", 4), Some(("b", 2)));
+    /// ```
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let index = match self.search(&key) {
+            Some(index) => index,
+            None if !self.is_full() => {
+                let index = self.len();
+                let node = Node {
+                    key,
+                    value,
+                    prev: END,
+                    next: END,
+                };
+                if self.entries.as_partial_slice_mut().try_push(node).is_err() {
+                    unreachable!("the cache is not full");
+                }
+                self.link_front(index);
+                return None;
+            }
+            // a cache without capacity cannot store anything
+            None if self.tail == END => return Some((key, value)),
+            None => self.tail,
+        };
+
+        self.promote(index);
+        let node = &mut self.entries[index];
+        let key = mem::replace(&mut node.key, key);
+        let value = mem::replace(&mut node.value, value);
+        Some((key, value))
+    }
+
+    /// Remove a key and return its value or [`None`], if it is missing.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.search(key)?;
+        Some(self.remove_index(index).1)
+    }
+}
+impl<K, V, const N: usize> Default for PartialLru<K, V, N> {
+    /// Initialize an empty [`PartialLru`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<K: Debug, V: Debug, const N: usize> Debug for PartialLru<K, V, N> {
+    /// Debug-format the entries from the most to the least recently used one.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<'a, K, V, const N: usize> IntoIterator for &'a PartialLru<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`PartialLru`] in recency order.
+///
+/// This struct is created by the [`iter`] method.
+///
+/// [`iter`]: PartialLru::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, K, V> {
+    entries: &'a [Node<K, V>],
+    /// The index of the next entry from the front (most recently used).
+    front: usize,
+    /// The index of the next entry from the back (least recently used).
+    back: usize,
+    /// The number of entries left, which is zero once `front` passed `back`.
+    remaining: usize,
+}
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let node = &self.entries[self.front];
+        self.front = node.next;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let node = &self.entries[self.back];
+        self.back = node.prev;
+        Some((&node.key, &node.value))
+    }
+}
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
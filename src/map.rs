@@ -0,0 +1,606 @@
+//! A fixed-capacity map.
+//!
+//! This module provides the [`PartialMap`], which stores up to `N` key-value
+//! pairs in a [`PartialArray`] sorted by their keys. This replaces linear scans
+//! over a `PartialArray<(K, V), N>` by binary searches, while still not
+//! allocating. Iteration is in ascending order of the keys, i.e. it is
+//! deterministic regardless of the insertion order:
+//! ```
+//! use partial_array::map::PartialMap;
+//!
+//! let mut config = PartialMap::<&str, u32, 8>::new();
+//! config.insert("baudrate", 9600);
+//! config.insert("address", 0x42);
+//! *config.entry("retries").or_insert(0) += 1;
+//!
+//! assert_eq!(config.get("baudrate"), Some(&9600));
+//! let keys: Vec<_> = config.keys().copied().collect();
+//! assert_eq!(keys, ["address", "baudrate", "retries"]);
+//! ```
+use crate::{CapacityError, PartialArray};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::Index;
+use core::slice;
+
+/// A map with up to `N` entries, which are sorted by their keys.
+///
+/// Lookups are binary searches and take O(log N) time, insertions and removals
+/// shift the following entries and take O(N) time. For the small tables this
+/// is intended for, this is usually faster than hashing.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PartialMap<K, V, const N: usize> {
+    /// The entries in ascending order of their (unique) keys.
+    entries: PartialArray<(K, V), N>,
+}
+impl<K, V, const N: usize> PartialMap<K, V, N> {
+    /// Create an empty [`PartialMap`].
+    pub const fn new() -> Self {
+        Self {
+            entries: PartialArray::new(),
+        }
+    }
+
+    /// The number of entries.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check, whether there are no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The maximum number of entries (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check, whether no more entries can be inserted.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Remove and drop all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterate over the entries in ascending order of the keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.entries.iter())
+    }
+
+    /// Iterate over the entries in ascending order of the keys with mutable
+    /// references to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut(self.entries.iter_mut())
+    }
+
+    /// Iterate over the keys in ascending order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.entries.iter())
+    }
+
+    /// Iterate over the values in ascending order of their keys.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values(self.entries.iter())
+    }
+
+    /// Remove the entry at `index` of the storage and return it.
+    fn remove_index(&mut self, index: usize) -> (K, V) {
+        self.entries.as_partial_slice_mut().remove(index)
+    }
+}
+impl<K: Ord, V, const N: usize> PartialMap<K, V, N> {
+    /// Build up a [`PartialMap`] from an iterator or return the first entry,
+    /// that does not fit.
+    ///
+    /// Like [`FromIterator`], later entries replace the values of earlier ones
+    /// with the same key, so only the number of distinct keys is limited.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::map::PartialMap;
+    /// let map = PartialMap::<u8, char, 2>::try_from_iter([(1, 'a'), (1, 'b'), (2, 'c')]);
+    /// assert_eq!(map.unwrap().get(&1), Some(&'b'));
+    ///
+    /// let map = PartialMap::<u8, char, 2>::try_from_iter([(1, 'a'), (2, 'b'), (3, 'c')]);
+    /// assert_eq!(map.unwrap_err().element(), (3, 'c'));
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<(K, V)>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut result = Self::new();
+        for (key, value) in iter {
+            result.try_insert(key, value)?;
+        }
+        Ok(result)
+    }
+
+    /// Search the storage index of a key.
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entries
+            .binary_search_by(|(probe, _)| probe.borrow().cmp(key))
+    }
+
+    /// Get a reference to the value of a key or [`None`], if it is missing.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    /// Get a mutable reference to the value of a key or [`None`], if it is
+    /// missing.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Get references to the stored key and the value or [`None`], if the key
+    /// is missing.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.search(key).ok()?;
+        let (key, value) = &self.entries[index];
+        Some((key, value))
+    }
+
+    /// Check, whether there is a value for the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(key).is_ok()
+    }
+
+    /// Insert a value for a key and return the previous value, if any.
+    ///
+    /// If the key is already present, the stored key is kept.
+    ///
+    /// # Panics
+    /// Panics, if the key is missing and the map is already full.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.try_insert(key, value) {
+            Ok(previous) => previous,
+            Err(_) => panic!("PartialMap is full (capacity {})", N),
+        }
+    }
+
+    /// Insert a value for a key and return the previous value, if any.
+    ///
+    /// If the key is missing and the map is full, the entry is returned in
+    /// the error instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::map::PartialMap;
+    /// let mut map = PartialMap::<u8, char, 1>::new();
+    ///
+    /// assert_eq!(map.try_insert(1, 'a').ok(), Some(None));
+    /// assert_eq!(map.try_insert(1, 'b').ok(), Some(Some('a')));
+    /// assert_eq!(map.try_insert(2, 'c').unwrap_err().element(), (2, 'c'));
+    /// ```
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Ok(Some(entry.insert(value))),
+            Entry::Vacant(entry) => entry.try_insert(value).map(|_| None),
+        }
+    }
+
+    /// Remove a key and return its value or [`None`], if it is missing.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Remove a key and return the stored key and its value or [`None`], if it
+    /// is missing.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.search(key).ok()?;
+        Some(self.remove_index(index))
+    }
+
+    /// Get the entry of a key for in-place manipulation.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::map::PartialMap;
+    /// let mut counts = PartialMap::<char, usize, 8>::new();
+    /// for c in "hello".chars() {
+    ///     *counts.entry(c).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(counts.get(&'l'), Some(&2));
+    /// assert_eq!(counts.len(), 4);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
+        match self.search(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                map: self,
+                index,
+                key,
+            }),
+        }
+    }
+}
+impl<K, V, const N: usize> Default for PartialMap<K, V, N> {
+    /// Initialize an empty [`PartialMap`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<K: Debug, V: Debug, const N: usize> Debug for PartialMap<K, V, N> {
+    /// Debug-format the entries in ascending order of the keys.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<K, V, Q, const N: usize> Index<&Q> for PartialMap<K, V, N>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    /// Get a reference to the value of a key.
+    ///
+    /// # Panics
+    /// Panics, if the key is missing.
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.get(key).expect("key not found")
+    }
+}
+impl<K: Ord, V, const N: usize> FromIterator<(K, V)> for PartialMap<K, V, N> {
+    /// Build up a [`PartialMap`] from an iterator.
+    ///
+    /// Later entries replace the values of earlier ones with the same key. See
+    /// [`PartialMap::try_from_iter`] for a non-panicking version.
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more than `N` distinct keys.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+impl<K: Ord, V, const N: usize> Extend<(K, V)> for PartialMap<K, V, N> {
+    /// Insert the entries of the iterator.
+    ///
+    /// # Panics
+    /// Panics, if a new key is inserted into a full map.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(key, value)| {
+            self.insert(key, value);
+        });
+    }
+}
+impl<K, V, const N: usize> From<PartialMap<K, V, N>> for PartialArray<(K, V), N> {
+    /// Get the entries in ascending order of the keys.
+    fn from(map: PartialMap<K, V, N>) -> Self {
+        map.entries
+    }
+}
+impl<K, V, const N: usize> IntoIterator for PartialMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = crate::iter::IntoIter<(K, V), N>;
+
+    /// Iterate over the entries by value in ascending order of the keys.
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+impl<'a, K, V, const N: usize> IntoIterator for &'a PartialMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut PartialMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An entry of a [`PartialMap`], which is either occupied or vacant.
+///
+/// This enum is created by the [`entry`] method.
+///
+/// [`entry`]: PartialMap::entry
+pub enum Entry<'a, K, V, const N: usize> {
+    /// The key is present.
+    Occupied(OccupiedEntry<'a, K, V, N>),
+    /// The key is missing.
+    Vacant(VacantEntry<'a, K, V, N>),
+}
+impl<'a, K, V, const N: usize> Entry<'a, K, V, N> {
+    /// Get a reference to the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Insert `default`, if the key is missing, and return a mutable reference
+    /// to the value.
+    ///
+    /// # Panics
+    /// Panics, if the key is missing and the map is already full.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default`, if the key is missing, and return a
+    /// mutable reference to the value.
+    ///
+    /// # Panics
+    /// Panics, if the key is missing and the map is already full.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Modify the value, if the key is present.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+impl<'a, K, V: Default, const N: usize> Entry<'a, K, V, N> {
+    /// Insert the default value, if the key is missing, and return a mutable
+    /// reference to the value.
+    ///
+    /// # Panics
+    /// Panics, if the key is missing and the map is already full.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+impl<K: Debug, V: Debug, const N: usize> Debug for Entry<'_, K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+            Self::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish(),
+        }
+    }
+}
+
+/// An occupied entry of a [`PartialMap`], see [`Entry`].
+pub struct OccupiedEntry<'a, K, V, const N: usize> {
+    map: &'a mut PartialMap<K, V, N>,
+    /// The storage index of the entry.
+    index: usize,
+}
+impl<'a, K, V, const N: usize> OccupiedEntry<'a, K, V, N> {
+    /// Get a reference to the stored key.
+    pub fn key(&self) -> &K {
+        &self.map.entries[self.index].0
+    }
+
+    /// Get a reference to the value.
+    pub fn get(&self) -> &V {
+        &self.map.entries[self.index].1
+    }
+
+    /// Get a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.entries[self.index].1
+    }
+
+    /// Convert into a mutable reference to the value, that lives as long as
+    /// the borrow of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.entries[self.index].1
+    }
+
+    /// Replace the value and return the previous one.
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the entry from the map and return the value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Remove the entry from the map and return the stored key and the value.
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_index(self.index)
+    }
+}
+impl<K: Debug, V: Debug, const N: usize> Debug for OccupiedEntry<'_, K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("OccupiedEntry")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+/// A vacant entry of a [`PartialMap`], see [`Entry`].
+pub struct VacantEntry<'a, K, V, const N: usize> {
+    map: &'a mut PartialMap<K, V, N>,
+    /// The storage index, where the key is inserted.
+    index: usize,
+    key: K,
+}
+impl<'a, K, V, const N: usize> VacantEntry<'a, K, V, N> {
+    /// Get a reference to the key.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert a value for the key and return a mutable reference to it.
+    ///
+    /// # Panics
+    /// Panics, if the map is already full.
+    pub fn insert(self, value: V) -> &'a mut V {
+        match self.try_insert(value) {
+            Ok(value) => value,
+            Err(_) => panic!("PartialMap is full (capacity {})", N),
+        }
+    }
+
+    /// Insert a value for the key and return a mutable reference to it or
+    /// return the entry, if the map is full.
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+        let entries = &mut self.map.entries;
+        entries
+            .as_partial_slice_mut()
+            .try_insert(self.index, (self.key, value))?;
+        Ok(&mut entries[self.index].1)
+    }
+}
+impl<K: Debug, V, const N: usize> Debug for VacantEntry<'_, K, V, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("VacantEntry").field(self.key()).finish()
+    }
+}
+
+/// An iterator over the entries of a [`PartialMap`].
+///
+/// This struct is created by the [`iter`] method.
+///
+/// [`iter`]: PartialMap::iter
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, K, V>(slice::Iter<'a, (K, V)>);
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, value)| (key, value))
+    }
+}
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// A mutable iterator over the entries of a [`PartialMap`].
+///
+/// This struct is created by the [`iter_mut`] method.
+///
+/// [`iter_mut`]: PartialMap::iter_mut
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, K, V>(slice::IterMut<'a, (K, V)>);
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (&*key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, value)| (&*key, value))
+    }
+}
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+/// An iterator over the keys of a [`PartialMap`].
+///
+/// This struct is created by the [`keys`] method.
+///
+/// [`keys`]: PartialMap::keys
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Keys<'a, K, V>(slice::Iter<'a, (K, V)>);
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, _)| key)
+    }
+}
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of a [`PartialMap`].
+///
+/// This struct is created by the [`values`] method.
+///
+/// [`values`]: PartialMap::values
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Values<'a, K, V>(slice::Iter<'a, (K, V)>);
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, value)| value)
+    }
+}
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
@@ -0,0 +1,199 @@
+//! Plain-old-data partial arrays for zero-copy byte views via [`bytemuck`].
+//!
+//! This module is only available with the `bytemuck` feature. A normal
+//! [`PartialArray`] cannot be viewed as raw bytes, as its non-filled elements
+//! are uninitialized and its counter is a platform-dependent `usize`. This
+//! module therefore provides the [`PodPartialArray`], which stores a `u32`
+//! counter followed by `N` (always initialized) elements. It can be used for
+//! DMA or shared-memory buffers and converted to and from a [`PartialArray`].
+//! ```
+//! # use partial_array::PartialArray;
+//! # use partial_array::pod::PodPartialArray;
+//! let array: PartialArray<u32, 4> = [1, 2].iter().copied().collect();
+//! let array = PodPartialArray::from(array);
+//! let bytes = bytemuck::bytes_of(&array);
+//! assert_eq!(bytes.len(), 4 + 4 * 4);
+//!
+//! let view = PodPartialArray::<u32, 4>::try_from_bytes(bytes).unwrap();
+//! assert_eq!(view.len(), 2);
+//! assert_eq!(*view, [1, 2]);
+//! ```
+use crate::PartialArray;
+use bytemuck::{Pod, PodCastError, Zeroable};
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+
+/// A partially filled array of plain-old-data elements, that can be viewed as
+/// raw bytes.
+///
+/// Unlike the [`PartialArray`], all `N` elements are initialized (non-filled
+/// ones are typically zero) and the number of filled elements is stored in a
+/// fixed-size `u32` in front of them (`#[repr(C)]`). Therefore it is [`Pod`],
+/// if the element type is a [`PodElement`].
+///
+/// As any bit pattern is valid for the counter, use [`try_from_bytes`] to view
+/// untrusted bytes, which validates the counter. Dereferencing a value with a
+/// counter greater than `N` panics.
+///
+/// [`try_from_bytes`]: PodPartialArray::try_from_bytes
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PodPartialArray<T, const N: usize> {
+    /// The number of filled entries inside the array.
+    filled: u32,
+    /// The storage for the items, non-filled items are ignored.
+    array: [T; N],
+}
+impl<T: PodElement, const N: usize> PodPartialArray<T, N> {
+    /// View a byte slice as a [`PodPartialArray`].
+    ///
+    /// This fails, if the byte slice has the wrong size or alignment or if the
+    /// stored counter is greater than `N`.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::pod::{PodPartialArray, TryFromBytesError};
+    /// let bytes: [u32; 3] = [1, 42, 0];
+    /// let array = PodPartialArray::<u32, 2>::try_from_bytes(bytemuck::bytes_of(&bytes));
+    /// assert_eq!(*array.unwrap(), [42]);
+    ///
+    /// let bytes: [u32; 3] = [3, 42, 0];
+    /// let array = PodPartialArray::<u32, 2>::try_from_bytes(bytemuck::bytes_of(&bytes));
+    /// assert_eq!(array.unwrap_err(), TryFromBytesError::Length(3));
+    /// ```
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<&Self, TryFromBytesError> {
+        let array: &Self = bytemuck::try_from_bytes(bytes).map_err(TryFromBytesError::Cast)?;
+        array.check_len()?;
+        Ok(array)
+    }
+
+    /// View a mutable byte slice as a [`PodPartialArray`].
+    ///
+    /// This fails, if the byte slice has the wrong size or alignment or if the
+    /// stored counter is greater than `N`.
+    pub fn try_from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, TryFromBytesError> {
+        let array: &mut Self =
+            bytemuck::try_from_bytes_mut(bytes).map_err(TryFromBytesError::Cast)?;
+        array.check_len()?;
+        Ok(array)
+    }
+
+    /// Check, that the counter is in bounds.
+    fn check_len(&self) -> Result<(), TryFromBytesError> {
+        match self.filled as usize {
+            len if len > N => Err(TryFromBytesError::Length(len)),
+            _ => Ok(()),
+        }
+    }
+}
+// SAFETY: the all-zero pattern is an empty array of zeroed elements, which are
+// valid by the bound.
+unsafe impl<T: Zeroable, const N: usize> Zeroable for PodPartialArray<T, N> {}
+// SAFETY: the type is `#[repr(C)]` and consists of a `u32` and `Pod` elements,
+// so any bit pattern is valid. The `PodElement` bound guarantees, that the
+// elements start at offset 4 and that there is no trailing padding, i.e. there
+// are no padding bytes at all.
+unsafe impl<T: PodElement, const N: usize> Pod for PodPartialArray<T, N> {}
+impl<T: Zeroable, const N: usize> Default for PodPartialArray<T, N> {
+    /// Initialize an empty [`PodPartialArray`] with zeroed storage.
+    fn default() -> Self {
+        Zeroable::zeroed()
+    }
+}
+impl<T, const N: usize> Deref for PodPartialArray<T, N> {
+    type Target = [T];
+
+    /// Dereference to the slice of filled elements (potentially less than `N`).
+    ///
+    /// # Panics
+    /// Panics, if the stored counter is greater than `N`.
+    fn deref(&self) -> &Self::Target {
+        &self.array[..self.filled as usize]
+    }
+}
+impl<T, const N: usize> DerefMut for PodPartialArray<T, N> {
+    /// Dereference to the slice of filled elements (potentially less than `N`).
+    ///
+    /// # Panics
+    /// Panics, if the stored counter is greater than `N`.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.array[..self.filled as usize]
+    }
+}
+impl<T: Debug, const N: usize> Debug for PodPartialArray<T, N> {
+    /// Debug-format the slice of filled elements (potentially less than `N`).
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for PodPartialArray<T, N> {
+    /// Compare the filled elements with a normal array.
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.deref() == &other[..]
+    }
+}
+impl<T: Zeroable + Copy, const N: usize> From<PartialArray<T, N>> for PodPartialArray<T, N> {
+    /// Convert a [`PartialArray`] into a [`PodPartialArray`].
+    ///
+    /// The non-filled elements are zeroed.
+    ///
+    /// # Panics
+    /// Panics, if the number of elements does not fit into the `u32` counter.
+    fn from(array: PartialArray<T, N>) -> Self {
+        let filled = u32::try_from(array.len()).expect("length exceeds the `u32` counter");
+        let mut result = Self {
+            filled,
+            ..Zeroable::zeroed()
+        };
+        result.copy_from_slice(&array);
+        result
+    }
+}
+impl<T: Copy, const N: usize> From<PodPartialArray<T, N>> for PartialArray<T, N> {
+    /// Convert a [`PodPartialArray`] into a [`PartialArray`].
+    ///
+    /// # Panics
+    /// Panics, if the stored counter is greater than `N`.
+    fn from(array: PodPartialArray<T, N>) -> Self {
+        array.iter().copied().collect()
+    }
+}
+
+// SAFETY: the all-zero pattern is a `PartialArray` with `filled == 0`, i.e. an
+// empty one. The storage may be anything, as it is `MaybeUninit`.
+unsafe impl<T, const N: usize> Zeroable for PartialArray<T, N> {}
+
+/// An element type, that can be stored in a [`Pod`] [`PodPartialArray`].
+///
+/// # Safety
+/// The size of the type must be a multiple of 4 and its alignment must be at
+/// most 4. This guarantees, that the elements directly follow the `u32` counter
+/// and that there is no trailing padding for any `N`.
+pub unsafe trait PodElement: Pod {}
+// SAFETY: all of those types have a size of 4 and an alignment of 4.
+unsafe impl PodElement for u32 {}
+// SAFETY: see above
+unsafe impl PodElement for i32 {}
+// SAFETY: see above
+unsafe impl PodElement for f32 {}
+// SAFETY: arrays of `PodElement`s have a size that is a multiple of the element
+// size (a multiple of 4) and the same alignment as the element.
+unsafe impl<T: PodElement, const M: usize> PodElement for [T; M] {}
+
+/// The error returned by [`PodPartialArray::try_from_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryFromBytesError {
+    /// The bytes have the wrong size or alignment.
+    Cast(PodCastError),
+    /// The stored counter is greater than the capacity.
+    Length(usize),
+}
+impl Display for TryFromBytesError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Cast(error) => write!(f, "invalid byte slice: {}", error),
+            Self::Length(len) => write!(f, "stored length {} exceeds the capacity", len),
+        }
+    }
+}
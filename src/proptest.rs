@@ -0,0 +1,88 @@
+//! Property-based testing support via [`proptest`].
+//!
+//! This module is only available with the `proptest` feature. It implements
+//! [`Arbitrary`] for [`PartialArray`]s and provides the [`partial_array`]
+//! strategy constructor for custom element strategies and length ranges.
+//! ```
+//! # use partial_array::PartialArray;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn sum_is_bounded(array in partial_array::proptest::partial_array::<_, 8>(0..10_u32, 2..=8)) {
+//!         prop_assert!(array.len() >= 2);
+//!         prop_assert!(array.iter().sum::<u32>() <= 8 * 9);
+//!     }
+//!
+//!     fn any_array(array in any::<PartialArray<u8, 4>>()) {
+//!         prop_assert!(array.len() <= 4);
+//!     }
+//! }
+//! # sum_is_bounded();
+//! # any_array();
+//! ```
+use crate::PartialArray;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use proptest::arbitrary::{any_with, Arbitrary};
+use proptest::collection::{SizeRange, VecStrategy};
+use proptest::strategy::{Map, NewTree, Strategy, Union};
+use proptest::test_runner::TestRunner;
+
+/// The inner strategy: a biased union of `Vec` strategies, converted into a
+/// [`PartialArray`].
+type Inner<S, const N: usize> = Map<
+    Union<VecStrategy<Arc<S>>>,
+    fn(Vec<<S as Strategy>::Value>) -> PartialArray<<S as Strategy>::Value, N>,
+>;
+
+/// A strategy generating [`PartialArray`]s.
+///
+/// This struct is created by the [`partial_array`] function.
+#[must_use = "strategies do nothing unless used"]
+#[derive(Debug)]
+pub struct PartialArrayStrategy<S: Strategy, const N: usize>(Inner<S, N>);
+impl<S: Strategy, const N: usize> Strategy for PartialArrayStrategy<S, N> {
+    type Tree = <Inner<S, N> as Strategy>::Tree;
+    type Value = PartialArray<S::Value, N>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner)
+    }
+}
+
+/// Create a strategy generating [`PartialArray`]s with elements generated by
+/// `element` and a length in `len`.
+///
+/// The length is biased towards the bounds of `len`: with a probability of
+/// one quarter each, the minimum or maximum length is generated.
+///
+/// # Panics
+/// Panics, if the maximum length of `len` is greater than `N`.
+pub fn partial_array<S: Strategy, const N: usize>(
+    element: S,
+    len: impl Into<SizeRange>,
+) -> PartialArrayStrategy<S, N> {
+    let len = len.into();
+    let (min, max) = len.start_end_incl();
+    assert!(max <= N, "length range exceeds the capacity of {}", N);
+
+    let element = Arc::new(element);
+    let union = Union::new_weighted(vec![
+        (1, proptest::collection::vec(Arc::clone(&element), min)),
+        (1, proptest::collection::vec(Arc::clone(&element), max)),
+        (2, proptest::collection::vec(element, len)),
+    ]);
+    let collect: fn(Vec<S::Value>) -> PartialArray<S::Value, N> = |vec| vec.into_iter().collect();
+    PartialArrayStrategy(union.prop_map(collect))
+}
+
+impl<T: Arbitrary, const N: usize> Arbitrary for PartialArray<T, N> {
+    type Parameters = T::Parameters;
+    type Strategy = PartialArrayStrategy<T::Strategy, N>;
+
+    /// Generate arbitrary [`PartialArray`]s with up to `N` elements.
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        partial_array(any_with::<T>(args), 0..=N)
+    }
+}
@@ -0,0 +1,35 @@
+//! Property-based testing support via [`quickcheck`].
+//!
+//! This module is only available with the `quickcheck` feature. It implements
+//! [`Arbitrary`] for [`PartialArray`]s, which are shrunk like a `Vec` of their
+//! elements.
+use crate::PartialArray;
+use alloc::boxed::Box;
+use quickcheck::{Arbitrary, Gen};
+
+impl<T: Arbitrary, const N: usize> Arbitrary for PartialArray<T, N> {
+    /// Generate an arbitrary [`PartialArray`] with up to `N` elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// use quickcheck::{Arbitrary, Gen};
+    ///
+    /// let array = PartialArray::<u8, 4>::arbitrary(&mut Gen::new(10));
+    /// assert!(array.len() <= 4);
+    /// ```
+    fn arbitrary(g: &mut Gen) -> Self {
+        let len = match g.choose(&[0, 1, 2, 3]) {
+            Some(0) => 0,
+            Some(1) => N,
+            _ => usize::arbitrary(g) % (N + 1),
+        };
+        (0..len).map(|_| T::arbitrary(g)).collect()
+    }
+
+    /// Shrink a [`PartialArray`] by removing elements or shrinking them.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        // shrinking a `Vec` never increases its length, so it always fits
+        Box::new(self.to_vec().shrink().map(|vec| vec.into_iter().collect()))
+    }
+}
@@ -0,0 +1,80 @@
+//! Parallel iteration support via [`rayon`].
+//!
+//! This module is only available with the `rayon` feature. It implements
+//! [`FromParallelIterator`] and [`ParallelExtend`] for [`PartialArray`]s, so
+//! that parallel iterators can be collected into them, as well as parallel
+//! iteration over references to [`PartialArray`]s.
+//! ```
+//! # use partial_array::PartialArray;
+//! use rayon::prelude::*;
+//!
+//! let squares: PartialArray<u32, 8> = (0..8_u32).into_par_iter().map(|x| x * x).collect();
+//! assert_eq!(squares, [0, 1, 4, 9, 16, 25, 36, 49]);
+//!
+//! let sum: u32 = squares.par_iter().sum();
+//! assert_eq!(sum, 140);
+//! ```
+use crate::PartialArray;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+impl<T: Send, const N: usize> FromParallelIterator<T> for PartialArray<T, N> {
+    /// Build up a [`PartialArray`] from a parallel iterator with potentially
+    /// less than `N` elements.
+    ///
+    /// The elements are stored in the order of the iterator.
+    ///
+    /// # Panics
+    /// Panics, if the length of the iterator is greater than the maximum length
+    /// of the array (`N`). Indexed parallel iterators are checked up front,
+    /// others only after collecting all of their elements.
+    fn from_par_iter<I: IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+        let mut result = Self::default();
+        result.par_extend(par_iter);
+        result
+    }
+}
+impl<T: Send, const N: usize> ParallelExtend<T> for PartialArray<T, N> {
+    /// Extend a [`PartialArray`] with the elements of a parallel iterator.
+    ///
+    /// The elements are appended in the order of the iterator.
+    ///
+    /// # Panics
+    /// Panics, if there are more elements than remaining space in the array.
+    /// Indexed parallel iterators are checked up front, others only after
+    /// collecting all of their elements.
+    fn par_extend<I: IntoParallelIterator<Item = T>>(&mut self, par_iter: I) {
+        let remaining = N - self.filled;
+        let par_iter = par_iter.into_par_iter();
+        if let Some(len) = par_iter.opt_len() {
+            assert!(
+                len <= remaining,
+                "Iterator has {} elements to much",
+                len - remaining
+            );
+        }
+
+        // rayon keeps the order of the elements when collecting into a `Vec`
+        let elements: Vec<T> = par_iter.collect();
+        self.extend(elements);
+    }
+}
+impl<'a, T: Sync, const N: usize> IntoParallelIterator for &'a PartialArray<T, N> {
+    type Iter = rayon::slice::Iter<'a, T>;
+    type Item = &'a T;
+
+    /// Iterate in parallel over the filled elements.
+    fn into_par_iter(self) -> Self::Iter {
+        self.deref().into_par_iter()
+    }
+}
+impl<'a, T: Send, const N: usize> IntoParallelIterator for &'a mut PartialArray<T, N> {
+    type Iter = rayon::slice::IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    /// Iterate in parallel over mutable references to the filled elements.
+    fn into_par_iter(self) -> Self::Iter {
+        self.deref_mut().into_par_iter()
+    }
+}
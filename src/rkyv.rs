@@ -0,0 +1,188 @@
+//! Zero-copy archiving of [`PartialArray`]s via [`rkyv`].
+//!
+//! This module is only available with the `rkyv` feature, which requires Rust
+//! 1.81 (like `rkyv` 0.8 itself) instead of the crate's minimum of 1.71. It
+//! provides the [`ArchivedPartialArray`] type, which is the archived form of a
+//! [`PartialArray<T, N>`]. Like the [`PartialArray`] itself, the archived form
+//! is stored inline: it consists of the number of filled elements followed by
+//! storage for `N` archived elements, so the archive has the same size
+//! regardless of the number of filled elements. It dereferences to a slice of
+//! the filled elements, so the contents can be used without deserialization.
+//! ```
+//! # use core::mem::MaybeUninit;
+//! # use partial_array::PartialArray;
+//! # use partial_array::rkyv::ArchivedPartialArray;
+//! use rkyv::api::low::{access, to_bytes_in_with_alloc};
+//! use rkyv::rancor::Failure;
+//! use rkyv::ser::{allocator::SubAllocator, writer::Buffer};
+//! use rkyv::util::Align;
+//!
+//! let array: PartialArray<u32, 8> = [4, 8, 15].iter().copied().collect();
+//!
+//! let mut output = Align([MaybeUninit::<u8>::uninit(); 256]);
+//! let mut alloc = [MaybeUninit::<u8>::uninit(); 256];
+//! let bytes = to_bytes_in_with_alloc::<_, _, Failure>(
+//!     &array,
+//!     Buffer::from(&mut *output),
+//!     SubAllocator::new(&mut alloc),
+//! )
+//! .unwrap();
+//!
+//! let archived = access::<ArchivedPartialArray<rkyv::Archived<u32>, 8>, Failure>(&bytes).unwrap();
+//! assert_eq!(archived.len(), 3);
+//! assert_eq!(archived[2], 15);
+//! ```
+//!
+//! Validation (via [`bytecheck`]) rejects archives, that claim to have more than
+//! `N` filled elements.
+//!
+//! [`bytecheck`]: rkyv::bytecheck
+use crate::PartialArray;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::mem::{self, MaybeUninit};
+use core::ops::Deref;
+use core::ptr;
+use rkyv::bytecheck::CheckBytes;
+use rkyv::primitive::ArchivedUsize;
+use rkyv::rancor::{Fallible, Source};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+/// The archived form of a [`PartialArray<T, N>`].
+///
+/// This has a fixed capacity of `N` elements and stores the number of filled
+/// elements in front of the (inline) element storage. It [deref]s to a slice of
+/// the filled elements.
+///
+/// Note, that `T` is the _archived_ element type, e.g. `ArchivedPartialArray<
+/// rkyv::Archived<u32>, N>` is the archived form of `PartialArray<u32, N>`.
+///
+/// [deref]: core::ops::Deref::deref
+#[repr(C)]
+pub struct ArchivedPartialArray<T, const N: usize> {
+    /// The number of filled entries inside the archived array.
+    ///
+    /// Each item in `0..filled` must be initialized. Others are zeroed by the
+    /// serializer. This must never be greater than `N`.
+    filled: ArchivedUsize,
+    /// The storage for the archived items.
+    array: [MaybeUninit<T>; N],
+}
+// SAFETY: the type is `#[repr(C)]` and only consists of an `ArchivedUsize`
+// (which is portable) and an array of `T`, which is portable by the bound.
+unsafe impl<T: Portable, const N: usize> Portable for ArchivedPartialArray<T, N> {}
+impl<T, const N: usize> Deref for ArchivedPartialArray<T, N> {
+    type Target = [T];
+
+    /// Dereference to the slice of filled elements (potentially less than `N`).
+    fn deref(&self) -> &Self::Target {
+        let slice = &self.array[..self.filled.to_native() as usize];
+        // SAFETY: the invariant is, that `0..self.filled` is initialized, so it
+        // is no UB reading those. The transmute itself is safe, since
+        // `MaybeUninit` is `#[repr(transparent)]`.
+        unsafe { mem::transmute::<&[MaybeUninit<T>], &[T]>(slice) }
+    }
+}
+impl<T: Debug, const N: usize> Debug for ArchivedPartialArray<T, N> {
+    /// Debug-format the slice of filled elements (potentially less than `N`).
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+impl<T, U, const N: usize, const M: usize> PartialEq<PartialArray<U, M>>
+    for ArchivedPartialArray<T, N>
+where
+    T: PartialEq<U>,
+{
+    /// Compare the filled elements of an archived and a normal [`PartialArray`].
+    fn eq(&self, other: &PartialArray<U, M>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T: Archive, const N: usize> Archive for PartialArray<T, N> {
+    type Archived = ArchivedPartialArray<T::Archived, N>;
+    type Resolver = PartialArray<T::Resolver, N>;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: the places are derived from the fields of the (valid and
+        // properly aligned) out pointer, so they are valid for writes.
+        unsafe {
+            let out_ptr = out.ptr();
+            let filled = ptr::addr_of_mut!((*out_ptr).filled);
+            Place::from_field_unchecked(out, filled)
+                .write(ArchivedUsize::from_native(self.len() as _));
+
+            let array = ptr::addr_of_mut!((*out_ptr).array).cast::<T::Archived>();
+            for (i, (value, resolver)) in self.iter().zip(resolver).enumerate() {
+                value.resolve(resolver, Place::from_field_unchecked(out, array.add(i)));
+            }
+        }
+    }
+}
+impl<T, S, const N: usize> Serialize<S> for PartialArray<T, N>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.iter()
+            .map(|value| value.serialize(serializer))
+            .collect()
+    }
+}
+impl<T, D, const N: usize> Deserialize<PartialArray<T, N>, D>
+    for ArchivedPartialArray<T::Archived, N>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<PartialArray<T, N>, D::Error> {
+        self.iter()
+            .map(|value| value.deserialize(deserializer))
+            .collect()
+    }
+}
+
+/// The error raised during validation, if an archive claims to hold more than
+/// `N` elements.
+#[derive(Debug)]
+struct CapacityExceeded {
+    filled: usize,
+    capacity: usize,
+}
+impl Display for CapacityExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "archived partial array has {} elements, but a capacity of only {}",
+            self.filled, self.capacity
+        )
+    }
+}
+impl core::error::Error for CapacityExceeded {}
+
+// SAFETY: `check_bytes` only returns `Ok` if the length is valid, not greater
+// than `N` and each of the elements in `0..filled` is a valid `T`, which is
+// exactly the invariant of `ArchivedPartialArray`.
+unsafe impl<T, C, const N: usize> CheckBytes<C> for ArchivedPartialArray<T, N>
+where
+    T: CheckBytes<C>,
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        let filled = ptr::addr_of!((*value).filled);
+        ArchivedUsize::check_bytes(filled, context)?;
+        let filled = (*filled).to_native() as usize;
+        if filled > N {
+            return Err(C::Error::new(CapacityExceeded {
+                filled,
+                capacity: N,
+            }));
+        }
+
+        let array = ptr::addr_of!((*value).array).cast::<T>();
+        (0..filled).try_for_each(|i| T::check_bytes(array.add(i), context))
+    }
+}
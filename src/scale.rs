@@ -0,0 +1,69 @@
+//! [SCALE] encoding of [`PartialArray`]s via [`parity_scale_codec`].
+//!
+//! This module is only available with the `parity-scale-codec` feature. A
+//! [`PartialArray`] is encoded like a `Vec<T>`, i.e. the compact-encoded number
+//! of filled elements followed by the filled elements. Therefore both formats
+//! are compatible with each other, as long as there are at most `N` elements.
+//!
+//! [SCALE]: https://docs.substrate.io/reference/scale-codec/
+use crate::PartialArray;
+use core::ops::Deref;
+use parity_scale_codec::{
+    Compact, CompactLen, Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input,
+    MaxEncodedLen, Output,
+};
+
+impl<T: Encode, const N: usize> Encode for PartialArray<T, N> {
+    fn size_hint(&self) -> usize {
+        self.deref().size_hint()
+    }
+
+    /// Encode the filled elements (potentially less than `N`).
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// use parity_scale_codec::Encode;
+    ///
+    /// let array: PartialArray<u8, 4> = (1..3).collect();
+    /// assert_eq!(array.encode(), [2 << 2, 1, 2]);
+    /// ```
+    fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+        self.deref().encode_to(dest)
+    }
+}
+impl<T: Encode, const N: usize> EncodeLike for PartialArray<T, N> {}
+impl<T: Decode, const N: usize> Decode for PartialArray<T, N> {
+    /// Decode a [`PartialArray`] with up to `N` elements.
+    ///
+    /// An input with more than `N` elements is rejected before decoding any
+    /// element.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// use parity_scale_codec::Decode;
+    ///
+    /// let array = PartialArray::<u8, 4>::decode(&mut &[2 << 2, 1, 2][..]).unwrap();
+    /// assert_eq!(array, [1, 2]);
+    ///
+    /// let too_long = PartialArray::<u8, 1>::decode(&mut &[2 << 2, 1, 2][..]);
+    /// assert!(too_long.is_err());
+    /// ```
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let Compact(len) = Compact::<u32>::decode(input)?;
+        if len as usize > N {
+            return Err("PartialArray: length exceeds capacity".into());
+        }
+
+        (0..len).map(|_| T::decode(input)).collect()
+    }
+}
+impl<T: DecodeWithMemTracking, const N: usize> DecodeWithMemTracking for PartialArray<T, N> {}
+impl<T: MaxEncodedLen, const N: usize> MaxEncodedLen for PartialArray<T, N> {
+    /// The maximum encoded length, i.e. the length prefix and `N` elements.
+    fn max_encoded_len() -> usize {
+        Compact::<u32>::compact_len(&(N as u32))
+            .saturating_add(N.saturating_mul(T::max_encoded_len()))
+    }
+}
@@ -0,0 +1,392 @@
+//! A fixed-capacity set.
+//!
+//! This module provides the [`PartialSet`], which stores up to `N` distinct
+//! elements in a [`PartialArray`] sorted ascendingly. Collecting into it
+//! removes duplicates, e.g. to get the distinct values of some channels:
+//! ```
+//! use partial_array::set::PartialSet;
+//!
+//! let channels = [12, 13, 8, 12, 255, 8, 8, 8];
+//! let distinct: PartialSet<u8, 8> = channels.iter().copied().collect();
+//! assert_eq!(distinct, [8, 12, 13, 255]);
+//! ```
+//! Sets can be combined via [`union`], [`intersection`], [`difference`] and
+//! [`symmetric_difference`]. The results are bounded by the capacity of the
+//! set as well, so the operations, which may exceed it, return a
+//! [`CapacityError`].
+//!
+//! [`union`]: PartialSet::union
+//! [`intersection`]: PartialSet::intersection
+//! [`difference`]: PartialSet::difference
+//! [`symmetric_difference`]: PartialSet::symmetric_difference
+use crate::sorted::{Merge, Side};
+use crate::{CapacityError, PartialArray};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{FromIterator, Peekable};
+use core::ops::Deref;
+use core::slice;
+
+/// A set of up to `N` distinct elements, which are sorted ascendingly.
+///
+/// Lookups are binary searches and take O(log N) time, insertions and removals
+/// shift the following elements and take O(N) time. The set operations merge
+/// the sorted elements in linear time. It [deref]s to the (sorted) slice of
+/// elements.
+///
+/// [deref]: core::ops::Deref::deref
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PartialSet<T, const N: usize> {
+    /// The distinct elements in ascending order.
+    elements: PartialArray<T, N>,
+}
+impl<T, const N: usize> PartialSet<T, N> {
+    /// Create an empty [`PartialSet`].
+    pub const fn new() -> Self {
+        Self {
+            elements: PartialArray::new(),
+        }
+    }
+
+    /// The number of elements.
+    pub const fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Check, whether there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// The maximum number of elements (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check, whether no more elements can be inserted.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Remove and drop all elements.
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// Convert into a (sorted) [`PartialArray`].
+    pub fn into_partial_array(self) -> PartialArray<T, N> {
+        self.elements
+    }
+}
+impl<T: Ord, const N: usize> PartialSet<T, N> {
+    /// Build up a [`PartialSet`] from an iterator or return the first element,
+    /// that does not fit.
+    ///
+    /// Like [`FromIterator`], duplicates are removed, so only the number of
+    /// distinct elements is limited.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::set::PartialSet;
+    /// let set = PartialSet::<u8, 2>::try_from_iter([1, 2, 1, 2]);
+    /// assert_eq!(set.unwrap(), [1, 2]);
+    ///
+    /// let set = PartialSet::<u8, 2>::try_from_iter([1, 2, 3]);
+    /// assert_eq!(set.unwrap_err().element(), 3);
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut result = Self::new();
+        for value in iter {
+            result.try_insert(value)?;
+        }
+        Ok(result)
+    }
+
+    /// Search the storage index of an element.
+    fn search<Q>(&self, value: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.elements
+            .binary_search_by(|probe| probe.borrow().cmp(value))
+    }
+
+    /// Insert an element and return, whether it was not contained before.
+    ///
+    /// If an equal element is already contained, the set is not modified.
+    ///
+    /// # Panics
+    /// Panics, if the element is new and the set is already full.
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.try_insert(value) {
+            Ok(new) => new,
+            Err(_) => panic!("PartialSet is full (capacity {})", N),
+        }
+    }
+
+    /// Insert an element and return, whether it was not contained before.
+    ///
+    /// If the element is new and the set is full, it is returned in the error
+    /// instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::set::PartialSet;
+    /// let mut set = PartialSet::<u8, 1>::new();
+    ///
+    /// assert_eq!(set.try_insert(1).ok(), Some(true));
+    /// assert_eq!(set.try_insert(1).ok(), Some(false));
+    /// assert_eq!(set.try_insert(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn try_insert(&mut self, value: T) -> Result<bool, CapacityError<T>> {
+        let index = match self.search(&value) {
+            Ok(_) => return Ok(false),
+            Err(index) => index,
+        };
+        self.elements
+            .as_partial_slice_mut()
+            .try_insert(index, value)?;
+        Ok(true)
+    }
+
+    /// Check, whether an element equal to `value` is contained.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(value).is_ok()
+    }
+
+    /// Get a reference to the element equal to `value` or [`None`], if it is
+    /// not contained.
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.search(value).ok()?;
+        Some(&self.elements[index])
+    }
+
+    /// Remove the element equal to `value` and return, whether it was
+    /// contained.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Remove the element equal to `value` and return it or [`None`], if it was
+    /// not contained.
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.search(value).ok()?;
+        Some(self.elements.as_partial_slice_mut().remove(index))
+    }
+
+    /// Check, whether all elements are contained in `other`.
+    pub fn is_subset<const M: usize>(&self, other: &PartialSet<T, M>) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    /// Check, whether all elements of `other` are contained in this set.
+    pub fn is_superset<const M: usize>(&self, other: &PartialSet<T, M>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Check, whether there are no elements in common with `other`.
+    pub fn is_disjoint<const M: usize>(&self, other: &PartialSet<T, M>) -> bool {
+        Membership::new(self, other).all(|(_, in_self, in_other)| !(in_self && in_other))
+    }
+}
+impl<T: Ord + Clone, const N: usize> PartialSet<T, N> {
+    /// Build a set of the elements contained in `self`, `other` or both.
+    ///
+    /// # Errors
+    /// Returns an error, if the union has more than `N` elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::set::PartialSet;
+    /// let a: PartialSet<u8, 4> = [1, 2].iter().copied().collect();
+    /// let b: PartialSet<u8, 4> = [2, 3].iter().copied().collect();
+    /// assert_eq!(a.union(&b).unwrap(), [1, 2, 3]);
+    ///
+    /// let c: PartialSet<u8, 4> = [4, 5].iter().copied().collect();
+    /// assert!(a.union(&b).unwrap().union(&c).is_err());
+    /// ```
+    pub fn union<const M: usize>(&self, other: &PartialSet<T, M>) -> Result<Self, CapacityError> {
+        Self::from_merge(self, other, |in_self, in_other| in_self || in_other)
+    }
+
+    /// Build a set of the elements contained in both `self` and `other`.
+    ///
+    /// This cannot exceed the capacity, as the result is a subset of `self`.
+    pub fn intersection<const M: usize>(&self, other: &PartialSet<T, M>) -> Self {
+        Self::from_merge(self, other, |in_self, in_other| in_self && in_other)
+            .expect("the intersection is a subset of self")
+    }
+
+    /// Build a set of the elements contained in `self`, but not in `other`.
+    ///
+    /// This cannot exceed the capacity, as the result is a subset of `self`.
+    pub fn difference<const M: usize>(&self, other: &PartialSet<T, M>) -> Self {
+        Self::from_merge(self, other, |in_self, in_other| in_self && !in_other)
+            .expect("the difference is a subset of self")
+    }
+
+    /// Build a set of the elements contained in either `self` or `other`, but
+    /// not in both.
+    ///
+    /// # Errors
+    /// Returns an error, if the symmetric difference has more than `N`
+    /// elements.
+    pub fn symmetric_difference<const M: usize>(
+        &self,
+        other: &PartialSet<T, M>,
+    ) -> Result<Self, CapacityError> {
+        Self::from_merge(self, other, |in_self, in_other| in_self != in_other)
+    }
+
+    /// Build a set of the elements of both sets, for which `keep` returns
+    /// `true` given whether the element is contained in `self` and `other`.
+    fn from_merge<const M: usize, F>(
+        this: &Self,
+        other: &PartialSet<T, M>,
+        mut keep: F,
+    ) -> Result<Self, CapacityError>
+    where
+        F: FnMut(bool, bool) -> bool,
+    {
+        let mut result = Self::new();
+        for (value, in_self, in_other) in Membership::new(this, other) {
+            if keep(in_self, in_other) {
+                // the merge yields the elements ascendingly, so pushing them to
+                // the back keeps the result sorted
+                result
+                    .elements
+                    .as_partial_slice_mut()
+                    .try_push(value.clone())
+                    .map_err(CapacityError::simplify)?;
+            }
+        }
+        Ok(result)
+    }
+}
+impl<T, const N: usize> Default for PartialSet<T, N> {
+    /// Initialize an empty [`PartialSet`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T, const N: usize> Deref for PartialSet<T, N> {
+    type Target = [T];
+
+    /// Dereference to the (sorted) slice of elements.
+    fn deref(&self) -> &Self::Target {
+        &self.elements
+    }
+}
+impl<T: Debug, const N: usize> Debug for PartialSet<T, N> {
+    /// Debug-format the elements in ascending order.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for PartialSet<T, N> {
+    /// Compare a [`PartialSet`] with a normal array (in ascending order).
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.deref() == &other[..]
+    }
+}
+impl<T: Ord, const N: usize> FromIterator<T> for PartialSet<T, N> {
+    /// Build up a [`PartialSet`] from an iterator, removing duplicates.
+    ///
+    /// See [`PartialSet::try_from_iter`] for a non-panicking version.
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more than `N` distinct elements.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+impl<T: Ord, const N: usize> Extend<T> for PartialSet<T, N> {
+    /// Insert the elements of the iterator.
+    ///
+    /// # Panics
+    /// Panics, if a new element is inserted into a full set.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| {
+            self.insert(value);
+        });
+    }
+}
+impl<T, const N: usize> From<PartialSet<T, N>> for PartialArray<T, N> {
+    /// Get the elements in ascending order.
+    fn from(set: PartialSet<T, N>) -> Self {
+        set.into_partial_array()
+    }
+}
+impl<T, const N: usize> IntoIterator for PartialSet<T, N> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<T, N>;
+
+    /// Iterate over the elements by value in ascending order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a PartialSet<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of two sets in ascending order.
+///
+/// Each element is yielded once together with whether it is contained in the
+/// left and the right set (the left element is preferred, if it is in both).
+struct Membership<'a, T: Ord> {
+    merged: Peekable<Merge<&'a T, slice::Iter<'a, T>, slice::Iter<'a, T>>>,
+}
+impl<'a, T: Ord> Membership<'a, T> {
+    fn new<const N: usize, const M: usize>(
+        left: &'a PartialSet<T, N>,
+        right: &'a PartialSet<T, M>,
+    ) -> Self {
+        Self {
+            merged: Merge::new(left, right).peekable(),
+        }
+    }
+}
+impl<'a, T: Ord> Iterator for Membership<'a, T> {
+    type Item = (&'a T, bool, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, side) = self.merged.next()?;
+        Some(match side {
+            // the elements of a set are distinct, so an equal element can only
+            // follow from the right set (as the left one is preferred on ties)
+            Side::Left => {
+                let in_right = self.merged.next_if(|(next, _)| next == &value).is_some();
+                (value, true, in_right)
+            }
+            Side::Right => (value, false, true),
+        })
+    }
+}
@@ -0,0 +1,441 @@
+//! A fixed-capacity arena with stable, generational keys.
+//!
+//! A [`PartialArray`] is compacting: removing an element shifts the following
+//! ones, so indices are not stable. This module provides the [`PartialSlab`],
+//! which stores up to `N` entries in fixed slots and hands out a [`Key`] for
+//! each of them. The key stays valid until its entry is removed. Vacant slots
+//! are reused, but each slot has a generation, which is incremented on every
+//! insertion and removal. Therefore a stale key of a removed entry does not
+//! refer to a new entry in the same slot:
+//! ```
+//! use partial_array::slab::PartialSlab;
+//!
+//! let mut connections = PartialSlab::<&str, 4>::new();
+//! let a = connections.insert("10.0.0.1");
+//! let b = connections.insert("10.0.0.2");
+//!
+//! assert_eq!(connections.remove(a), Some("10.0.0.1"));
+//! let c = connections.insert("10.0.0.3");
+//! assert_eq!(c.index(), a.index());
+//!
+//! assert_eq!(connections.get(a), None);
+//! assert_eq!(connections.get(b), Some(&"10.0.0.2"));
+//! assert_eq!(connections.get(c), Some(&"10.0.0.3"));
+//! ```
+//!
+//! [`PartialArray`]: crate::PartialArray
+use crate::CapacityError;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{Enumerate, FusedIterator, Zip};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// A stable handle to an entry of a [`PartialSlab`].
+///
+/// A key consists of the index of the slot and the generation of the slot at
+/// the time of the insertion. It is only valid for the slab, that returned it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Key {
+    index: usize,
+    generation: u32,
+}
+impl Key {
+    /// The index of the slot.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The generation of the slot at the time of the insertion.
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// The end of the free list.
+const END: usize = usize::MAX;
+
+/// Check, whether a slot with the given generation is occupied.
+const fn is_occupied(generation: u32) -> bool {
+    generation % 2 == 1
+}
+
+/// An arena of up to `N` entries, which are addressed by generational [`Key`]s.
+///
+/// Inserting, accessing and removing entries takes O(1) time. Iterating takes
+/// time proportional to the number of slots used so far (not the number of
+/// entries).
+///
+/// The generation of a slot is a wrapping `u32`, so a stale key could refer to
+/// a new entry again after 2³¹ reuses of the same slot.
+pub struct PartialSlab<T, const N: usize> {
+    /// The number of occupied slots.
+    len: usize,
+    /// The number of slots, that have ever been occupied.
+    ///
+    /// The slots `used..N` are vacant and not part of the free list. This must
+    /// never be greater than `N`.
+    used: usize,
+    /// The index of the first vacant slot in the free list or `END`.
+    free: usize,
+    /// The generation of each slot, which is odd, if the slot is occupied.
+    ///
+    /// The value of each occupied slot must be initialized.
+    generations: [u32; N],
+    /// The index of the next vacant slot in the free list (for vacant slots).
+    next_free: [usize; N],
+    /// The storage for the values.
+    values: [MaybeUninit<T>; N],
+}
+impl<T, const N: usize> PartialSlab<T, N> {
+    /// Required for `MaybeUninit::uninit()` in array initializers
+    const UNINIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    /// Create an empty [`PartialSlab`].
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            used: 0,
+            free: END,
+            generations: [0; N],
+            next_free: [END; N],
+            values: [Self::UNINIT; N],
+        }
+    }
+
+    /// The number of entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check, whether there are no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The maximum number of entries (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check, whether no more entries can be inserted.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Insert a value and return its key.
+    ///
+    /// # Panics
+    /// Panics, if the slab is already full.
+    pub fn insert(&mut self, value: T) -> Key {
+        match self.try_insert(value) {
+            Ok(key) => key,
+            Err(_) => panic!("PartialSlab is full (capacity {})", N),
+        }
+    }
+
+    /// Insert a value and return its key or return the value, if the slab is
+    /// full.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::slab::PartialSlab;
+    /// let mut slab = PartialSlab::<u8, 1>::new();
+    ///
+    /// assert!(slab.try_insert(1).is_ok());
+    /// assert_eq!(slab.try_insert(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn try_insert(&mut self, value: T) -> Result<Key, CapacityError<T>> {
+        let index = if self.free != END {
+            let index = self.free;
+            self.free = self.next_free[index];
+            index
+        } else if self.used < N {
+            self.used += 1;
+            self.used - 1
+        } else {
+            return Err(CapacityError::new(value));
+        };
+
+        self.values[index] = MaybeUninit::new(value);
+        let generation = self.generations[index].wrapping_add(1);
+        self.generations[index] = generation;
+        self.len += 1;
+        Ok(Key { index, generation })
+    }
+
+    /// Check, whether the key refers to an entry of this slab.
+    pub fn contains(&self, key: Key) -> bool {
+        key.index < self.used && self.generations[key.index] == key.generation
+    }
+
+    /// Get a reference to the value of a key or [`None`], if the key is stale.
+    pub fn get(&self, key: Key) -> Option<&T> {
+        if !self.contains(key) {
+            return None;
+        }
+        // SAFETY: the generation of the slot is the odd generation of the key,
+        // so the slot is occupied and therefore initialized.
+        Some(unsafe { self.values[key.index].assume_init_ref() })
+    }
+
+    /// Get a mutable reference to the value of a key or [`None`], if the key
+    /// is stale.
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        if !self.contains(key) {
+            return None;
+        }
+        // SAFETY: see `get()`
+        Some(unsafe { self.values[key.index].assume_init_mut() })
+    }
+
+    /// Remove the entry of a key and return its value or [`None`], if the key
+    /// is stale.
+    ///
+    /// The key (and any copy of it) is stale afterwards.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        if !self.contains(key) {
+            return None;
+        }
+        Some(self.remove_index(key.index))
+    }
+
+    /// Remove the value of an occupied slot and add the slot to the free list.
+    fn remove_index(&mut self, index: usize) -> T {
+        debug_assert!(is_occupied(self.generations[index]));
+
+        let value = mem::replace(&mut self.values[index], Self::UNINIT);
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.next_free[index] = self.free;
+        self.free = index;
+        self.len -= 1;
+        // SAFETY: the slot was occupied and is vacant now, so the value is
+        // initialized and not read or dropped again.
+        unsafe { value.assume_init() }
+    }
+
+    /// Keep only the entries, for which `keep` returns `true`.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::slab::PartialSlab;
+    /// let mut timers = PartialSlab::<u32, 4>::new();
+    /// let short = timers.insert(10);
+    /// let long = timers.insert(1000);
+    ///
+    /// timers.retain(|_, remaining| *remaining > 100);
+    /// assert!(!timers.contains(short));
+    /// assert!(timers.contains(long));
+    /// ```
+    pub fn retain<F: FnMut(Key, &mut T) -> bool>(&mut self, mut keep: F) {
+        for index in 0..self.used {
+            let generation = self.generations[index];
+            if !is_occupied(generation) {
+                continue;
+            }
+
+            // SAFETY: the slot is occupied and therefore initialized.
+            let value = unsafe { self.values[index].assume_init_mut() };
+            if !keep(Key { index, generation }, value) {
+                drop(self.remove_index(index));
+            }
+        }
+    }
+
+    /// Remove and drop all entries.
+    ///
+    /// Like for [`remove`], the keys of the entries are stale afterwards.
+    ///
+    /// [`remove`]: PartialSlab::remove
+    pub fn clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+
+    /// Iterate over the keys and values of the entries in the order of the
+    /// slots.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: self.generations[..self.used]
+                .iter()
+                .zip(self.values[..self.used].iter())
+                .enumerate(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterate over the keys and mutable references to the values of the
+    /// entries in the order of the slots.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.generations[..self.used]
+                .iter()
+                .zip(self.values[..self.used].iter_mut())
+                .enumerate(),
+            remaining: self.len,
+        }
+    }
+}
+impl<T, const N: usize> Drop for PartialSlab<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+impl<T, const N: usize> Default for PartialSlab<T, N> {
+    /// Initialize an empty [`PartialSlab`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone, const N: usize> Clone for PartialSlab<T, N> {
+    /// Clone a [`PartialSlab`], including the keys of its entries.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::slab::PartialSlab;
+    /// let mut a = PartialSlab::<u8, 4>::new();
+    /// let key = a.insert(42);
+    ///
+    /// let b = a.clone();
+    /// assert_eq!(b.get(key), Some(&42));
+    /// ```
+    fn clone(&self) -> Self {
+        let mut result = Self::new();
+        result.free = self.free;
+        result.next_free = self.next_free;
+        for (index, &generation) in self.generations[..self.used].iter().enumerate() {
+            if is_occupied(generation) {
+                // SAFETY: the slot is occupied and therefore initialized.
+                let value = unsafe { self.values[index].assume_init_ref() };
+                result.values[index] = MaybeUninit::new(value.clone());
+                result.len += 1;
+            }
+            // mark the slot as used only after it is initialized, so that a
+            // panicking `clone()` does not drop uninitialized values
+            result.generations[index] = generation;
+            result.used = index + 1;
+        }
+        result
+    }
+}
+impl<T: Debug, const N: usize> Debug for PartialSlab<T, N> {
+    /// Debug-format the entries as a map from their keys to their values.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<T, const N: usize> Index<Key> for PartialSlab<T, N> {
+    type Output = T;
+
+    /// Get a reference to the value of a key.
+    ///
+    /// # Panics
+    /// Panics, if the key is stale.
+    fn index(&self, key: Key) -> &Self::Output {
+        self.get(key).expect("invalid key")
+    }
+}
+impl<T, const N: usize> IndexMut<Key> for PartialSlab<T, N> {
+    /// Get a mutable reference to the value of a key.
+    ///
+    /// # Panics
+    /// Panics, if the key is stale.
+    fn index_mut(&mut self, key: Key) -> &mut Self::Output {
+        self.get_mut(key).expect("invalid key")
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a PartialSlab<T, N> {
+    type Item = (Key, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a mut PartialSlab<T, N> {
+    type Item = (Key, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the entries of a [`PartialSlab`].
+///
+/// This struct is created by the [`iter`] method.
+///
+/// [`iter`]: PartialSlab::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T> {
+    /// The used slots.
+    slots: Enumerate<Zip<slice::Iter<'a, u32>, slice::Iter<'a, MaybeUninit<T>>>>,
+    /// The number of occupied slots left.
+    remaining: usize,
+}
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Key, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, (&generation, value)) = self
+            .slots
+            .by_ref()
+            .find(|(_, (&generation, _))| is_occupied(generation))?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied and therefore initialized.
+        let value = unsafe { value.assume_init_ref() };
+        Some((Key { index, generation }, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> FusedIterator for Iter<'_, T> {}
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A mutable iterator over the entries of a [`PartialSlab`].
+///
+/// This struct is created by the [`iter_mut`] method.
+///
+/// [`iter_mut`]: PartialSlab::iter_mut
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, T> {
+    /// The used slots.
+    slots: Enumerate<Zip<slice::Iter<'a, u32>, slice::IterMut<'a, MaybeUninit<T>>>>,
+    /// The number of occupied slots left.
+    remaining: usize,
+}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Key, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, (&generation, value)) = self
+            .slots
+            .by_ref()
+            .find(|(_, (&generation, _))| is_occupied(generation))?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied and therefore initialized.
+        let value = unsafe { value.assume_init_mut() };
+        Some((Key { index, generation }, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> FusedIterator for IterMut<'_, T> {}
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("IterMut")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
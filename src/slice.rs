@@ -0,0 +1,189 @@
+//! A view of [`PartialArray`]s, that is independent of the capacity.
+//!
+//! This module provides the [`PartialSlice`] type, which relates to a
+//! [`PartialArray<T, N>`] like a slice relates to an array: it is unsized and
+//! stores the capacity `N` in the (fat) reference instead of the type. This
+//! allows writing non-generic functions (or trait objects), that append to any
+//! [`PartialArray`] regardless of its capacity:
+//! ```
+//! # use partial_array::PartialArray;
+//! use partial_array::slice::PartialSlice;
+//!
+//! fn fill(slice: &mut PartialSlice<u8>) {
+//!     while slice.try_push(42).is_ok() {}
+//! }
+//!
+//! let mut small = PartialArray::<u8, 2>::default();
+//! let mut large = PartialArray::<u8, 8>::default();
+//! fill(small.as_partial_slice_mut());
+//! fill(large.as_partial_slice_mut());
+//! assert_eq!(small.len(), 2);
+//! assert_eq!(large.len(), 8);
+//! ```
+use crate::{storage, CapacityError, PartialArray};
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+/// A capacity-independent view of a [`PartialArray`].
+///
+/// This is an unsized type, that can only be used behind a reference, which is
+/// obtained via [`PartialArray::as_partial_slice`] or
+/// [`PartialArray::as_partial_slice_mut`]. Like the [`PartialArray`] it [deref]s
+/// to the slice of filled elements, but it also allows to add and remove
+/// elements.
+///
+/// [deref]: core::ops::Deref::deref
+#[repr(C)]
+pub struct PartialSlice<T> {
+    /// The number of filled entries inside the storage.
+    ///
+    /// Each item in `0..filled` must be initialized. Others may or may not.
+    /// This must never be greater than the length of `array`.
+    filled: usize,
+    /// The storage of the viewed [`PartialArray`].
+    array: [MaybeUninit<T>],
+}
+impl<T> PartialSlice<T> {
+    /// Create a view of the given [`PartialArray`].
+    pub(crate) fn new<const N: usize>(array: &PartialArray<T, N>) -> &Self {
+        let storage = ptr::slice_from_raw_parts(array as *const _ as *const MaybeUninit<T>, N);
+        // SAFETY: both types are `#[repr(C)]` with a `usize` counter followed by
+        // the storage, so the fields are at the same offsets (the alignment of
+        // an array equals the one of its elements). The metadata of the fat
+        // pointer is the length of the storage, i.e. `N`. The invariants are
+        // the same, too.
+        unsafe { &*(storage as *const Self) }
+    }
+
+    /// Create a mutable view of the given [`PartialArray`].
+    pub(crate) fn new_mut<const N: usize>(array: &mut PartialArray<T, N>) -> &mut Self {
+        let storage = ptr::slice_from_raw_parts_mut(array as *mut _ as *mut MaybeUninit<T>, N);
+        // SAFETY: see `new()`
+        unsafe { &mut *(storage as *mut Self) }
+    }
+
+    /// The maximum number of elements (the `N` of the viewed array).
+    pub fn capacity(&self) -> usize {
+        self.array.len()
+    }
+
+    /// Check, whether no more elements can be added.
+    pub fn is_full(&self) -> bool {
+        self.filled == self.capacity()
+    }
+
+    /// Append an element to the back.
+    ///
+    /// # Panics
+    /// Panics, if the slice is already full.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::partial_array;
+    /// let mut array = partial_array![1, 2, 0];
+    /// array.pop();
+    ///
+    /// array.as_partial_slice_mut().push(3);
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            panic!("PartialSlice is full (capacity {})", self.capacity());
+        }
+    }
+
+    /// Append an element to the back or return it, if the slice is full.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::PartialArray;
+    /// let mut array = PartialArray::<u8, 1>::default();
+    /// let slice = array.as_partial_slice_mut();
+    ///
+    /// assert!(slice.try_push(1).is_ok());
+    /// assert_eq!(slice.try_push(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        storage::try_push(&mut self.filled, &mut self.array, value)
+    }
+
+    /// Remove the last element and return it or [`None`], if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::pop(&mut self.filled, &mut self.array) }
+    }
+
+    /// Shorten the slice to the first `len` elements and drop the rest.
+    ///
+    /// This has no effect, if the slice has `len` or less elements.
+    pub fn truncate(&mut self, len: usize) {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::truncate(&mut self.filled, &mut self.array, len) }
+    }
+
+    /// Remove and drop all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Insert an element at `index` and shift the following elements to the
+    /// back or return it, if the slice is full.
+    ///
+    /// # Panics
+    /// Panics, if `index` is greater than the number of elements.
+    pub(crate) fn try_insert(&mut self, index: usize, value: T) -> Result<(), CapacityError<T>> {
+        assert!(index <= self.filled, "index out of bounds");
+        self.try_push(value)?;
+        self[index..].rotate_right(1);
+        Ok(())
+    }
+
+    /// Remove the element at `index` and shift the following elements to the
+    /// front.
+    ///
+    /// # Panics
+    /// Panics, if `index` is out of bounds.
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.filled, "index out of bounds");
+        // moving the element to the back keeps the order of the others
+        self[index..].rotate_left(1);
+        self.pop().expect("the slice is not empty")
+    }
+}
+impl<T> Deref for PartialSlice<T> {
+    type Target = [T];
+
+    /// Dereference to the slice of filled elements.
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::initialized(&self.array, self.filled) }
+    }
+}
+impl<T> DerefMut for PartialSlice<T> {
+    /// Dereference to the slice of filled elements.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: the invariant is, that `0..self.filled` is initialized.
+        unsafe { storage::initialized_mut(&mut self.array, self.filled) }
+    }
+}
+impl<T: Debug> Debug for PartialSlice<T> {
+    /// Debug-format the slice of filled elements.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+impl<T: PartialEq> PartialEq for PartialSlice<T> {
+    /// Compare the filled elements of [`PartialSlice`]s.
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+impl<T: PartialEq, const M: usize> PartialEq<[T; M]> for PartialSlice<T> {
+    /// Compare a [`PartialSlice`] with a normal array.
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.deref() == &other[..]
+    }
+}
+impl<T: Eq> Eq for PartialSlice<T> {}
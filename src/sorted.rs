@@ -0,0 +1,363 @@
+//! Partial arrays, that keep their elements sorted.
+//!
+//! This module provides the [`SortedPartialArray`], which wraps a
+//! [`PartialArray`] and inserts new elements at their sorted position. This is
+//! useful for small lookup tables, e.g. thresholds or routing entries, which
+//! would otherwise be re-sorted after every insertion:
+//! ```
+//! use partial_array::sorted::SortedPartialArray;
+//!
+//! let mut thresholds = SortedPartialArray::<u16, 8>::new();
+//! thresholds.insert(500);
+//! thresholds.insert(100);
+//! thresholds.insert(250);
+//!
+//! assert_eq!(thresholds, [100, 250, 500]);
+//! assert!(thresholds.contains(&250));
+//! assert_eq!(thresholds.range(200..), [250, 500]);
+//! ```
+use crate::{storage, CapacityError, PartialArray};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{FromIterator, Peekable};
+use core::ops::{Bound, Deref, RangeBounds};
+use core::slice;
+
+/// A potentially partially filled array, whose elements are sorted ascendingly.
+///
+/// Elements are inserted via binary search and shifting the greater elements,
+/// i.e. in O(N) time. Elements added via [`insert`] (or [`Extend`]) are placed
+/// after equal elements, so those are kept in insertion order. It [deref]s to a
+/// slice of the elements, but only allows mutations, that keep the elements
+/// sorted.
+///
+/// [`insert`]: SortedPartialArray::insert
+/// [deref]: core::ops::Deref::deref
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SortedPartialArray<T, const N: usize>(PartialArray<T, N>);
+impl<T, const N: usize> SortedPartialArray<T, N> {
+    /// Create an empty [`SortedPartialArray`].
+    pub const fn new() -> Self {
+        Self(PartialArray::new())
+    }
+
+    /// The number of elements.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check, whether there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The maximum number of elements (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check, whether no more elements can be inserted.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Remove the element at `index` and return it.
+    ///
+    /// # Panics
+    /// Panics, if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.0.as_partial_slice_mut().remove(index)
+    }
+
+    /// Remove the greatest element and return it or [`None`], if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    /// Shorten the array to the `len` smallest elements and drop the rest.
+    ///
+    /// This has no effect, if the array has `len` or less elements.
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    /// Remove and drop all elements.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Convert into a (sorted) [`PartialArray`].
+    pub fn into_partial_array(self) -> PartialArray<T, N> {
+        self.0
+    }
+}
+impl<T: Ord, const N: usize> SortedPartialArray<T, N> {
+    /// Insert an element at its sorted position and return that index.
+    ///
+    /// The element is inserted after all equal elements.
+    ///
+    /// # Panics
+    /// Panics, if the array is already full.
+    pub fn insert(&mut self, value: T) -> usize {
+        match self.try_insert(value) {
+            Ok(index) => index,
+            Err(_) => panic!("SortedPartialArray is full (capacity {})", N),
+        }
+    }
+
+    /// Insert an element at its sorted position or return it, if the array is
+    /// full.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sorted::SortedPartialArray;
+    /// let mut array = SortedPartialArray::<u8, 2>::new();
+    ///
+    /// assert_eq!(array.try_insert(7).ok(), Some(0));
+    /// assert_eq!(array.try_insert(3).ok(), Some(0));
+    /// assert_eq!(array.try_insert(5).unwrap_err().element(), 5);
+    /// assert_eq!(array, [3, 7]);
+    /// ```
+    pub fn try_insert(&mut self, value: T) -> Result<usize, CapacityError<T>> {
+        let index = self.0.partition_point(|element| element <= &value);
+        self.0.as_partial_slice_mut().try_insert(index, value)?;
+        Ok(index)
+    }
+
+    /// Check, whether an element equal to `value` is contained.
+    ///
+    /// This is a binary search, i.e. it takes O(log N) time.
+    pub fn contains(&self, value: &T) -> bool {
+        self.0.binary_search(value).is_ok()
+    }
+
+    /// Remove an element equal to `value` and return it or [`None`], if there
+    /// is no such element.
+    ///
+    /// If there are multiple equal elements, any one of them is removed.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sorted::SortedPartialArray;
+    /// let mut array: SortedPartialArray<u8, 4> = [3, 1, 2].iter().copied().collect();
+    ///
+    /// assert_eq!(array.remove_value(&2), Some(2));
+    /// assert_eq!(array.remove_value(&2), None);
+    /// assert_eq!(array, [1, 3]);
+    /// ```
+    pub fn remove_value(&mut self, value: &T) -> Option<T> {
+        let index = self.0.binary_search(value).ok()?;
+        Some(self.remove(index))
+    }
+
+    /// Get the (sorted) slice of elements within the given range of values.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sorted::SortedPartialArray;
+    /// let array: SortedPartialArray<u8, 8> = (0..8).rev().collect();
+    ///
+    /// assert_eq!(array.range(2..5), [2, 3, 4]);
+    /// assert_eq!(array.range(..=1), [0, 1]);
+    /// assert_eq!(array.range(9..), []);
+    /// ```
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> &[T] {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.0.partition_point(|element| element < start),
+            Bound::Excluded(start) => self.0.partition_point(|element| element <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.0.partition_point(|element| element <= end),
+            Bound::Excluded(end) => self.0.partition_point(|element| element < end),
+            Bound::Unbounded => self.len(),
+        };
+        // an empty range (e.g. `5..2`) may yield an end before the start
+        &self.0[start..end.max(start)]
+    }
+
+    /// Merge the elements of another [`SortedPartialArray`] into this one.
+    ///
+    /// This takes O(N + M) time. Equal elements of `self` are placed before the
+    /// ones of `other`. See [`try_merge`] for a non-panicking version.
+    ///
+    /// # Panics
+    /// Panics, if both arrays together have more elements than the capacity
+    /// (`N`).
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sorted::SortedPartialArray;
+    /// let a: SortedPartialArray<u8, 6> = [1, 4, 5].iter().copied().collect();
+    /// let b: SortedPartialArray<u8, 3> = [2, 3, 6].iter().copied().collect();
+    ///
+    /// assert_eq!(a.merge(b), [1, 2, 3, 4, 5, 6]);
+    /// ```
+    ///
+    /// [`try_merge`]: SortedPartialArray::try_merge
+    pub fn merge<const M: usize>(self, other: SortedPartialArray<T, M>) -> Self {
+        match self.try_merge(other) {
+            Ok(merged) => merged,
+            Err(_) => panic!("merged arrays have more elements than the capacity"),
+        }
+    }
+
+    /// Merge the elements of another [`SortedPartialArray`] into this one or
+    /// return both arrays, if they have more elements than the capacity (`N`).
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sorted::SortedPartialArray;
+    /// let a: SortedPartialArray<u8, 4> = [1, 4].iter().copied().collect();
+    /// let b: SortedPartialArray<u8, 4> = [2, 3].iter().copied().collect();
+    /// let merged = a.try_merge(b).unwrap();
+    /// assert_eq!(merged, [1, 2, 3, 4]);
+    ///
+    /// // the arrays are returned in the error, if they do not fit
+    /// let copy = merged.clone();
+    /// let (merged, copy) = merged.try_merge(copy).unwrap_err().element();
+    /// assert_eq!(merged, copy);
+    /// ```
+    pub fn try_merge<const M: usize>(
+        self,
+        other: SortedPartialArray<T, M>,
+    ) -> Result<Self, CapacityError<(Self, SortedPartialArray<T, M>)>> {
+        if self.len() + other.len() > N {
+            return Err(CapacityError::new((self, other)));
+        }
+
+        Ok(Self(
+            Merge::new(self.0, other.0)
+                .map(|(element, _)| element)
+                .collect(),
+        ))
+    }
+}
+impl<T, const N: usize> Default for SortedPartialArray<T, N> {
+    /// Initialize an empty [`SortedPartialArray`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T, const N: usize> Deref for SortedPartialArray<T, N> {
+    type Target = [T];
+
+    /// Dereference to the (sorted) slice of elements.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T: Debug, const N: usize> Debug for SortedPartialArray<T, N> {
+    /// Debug-format the (sorted) slice of elements.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for SortedPartialArray<T, N> {
+    /// Compare a [`SortedPartialArray`] with a normal array.
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.deref() == &other[..]
+    }
+}
+impl<T: PartialEq, const N: usize> PartialEq<&[T]> for SortedPartialArray<T, N> {
+    /// Compare a [`SortedPartialArray`] with a slice.
+    fn eq(&self, other: &&[T]) -> bool {
+        self.deref() == *other
+    }
+}
+impl<T: Ord, const N: usize> From<PartialArray<T, N>> for SortedPartialArray<T, N> {
+    /// Sort the elements of a [`PartialArray`].
+    ///
+    /// The order of equal elements is unspecified.
+    fn from(mut array: PartialArray<T, N>) -> Self {
+        array.sort_unstable();
+        Self(array)
+    }
+}
+impl<T, const N: usize> From<SortedPartialArray<T, N>> for PartialArray<T, N> {
+    fn from(sorted: SortedPartialArray<T, N>) -> Self {
+        sorted.into_partial_array()
+    }
+}
+impl<T: Ord, const N: usize> FromIterator<T> for SortedPartialArray<T, N> {
+    /// Build up a [`SortedPartialArray`] from an iterator with potentially less
+    /// than `N` elements.
+    ///
+    /// The order of equal elements is unspecified.
+    ///
+    /// # Panics
+    /// Panics, if the length of the iterator is greater than the maximum length
+    /// of the array (`N`).
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PartialArray::from_iter(iter).into()
+    }
+}
+impl<T: Ord, const N: usize> Extend<T> for SortedPartialArray<T, N> {
+    /// Insert the elements of the iterator at their sorted positions.
+    ///
+    /// # Panics
+    /// Panics, if the iterator has more elements than there is space left.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        storage::extend_with(N - self.len(), iter, |element| {
+            self.insert(element);
+        });
+    }
+}
+impl<T, const N: usize> IntoIterator for SortedPartialArray<T, N> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<T, N>;
+
+    /// Iterate over the elements by value in ascending order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a SortedPartialArray<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The input of a [`Merge`], that an element originates from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    Left,
+    Right,
+}
+
+/// An iterator merging two sorted iterators (preferring `left` on ties).
+///
+/// Each element is yielded together with the [`Side`] it originates from, so
+/// equal elements of both sides are yielded consecutively, the left one first.
+pub(crate) struct Merge<T, L: Iterator<Item = T>, R: Iterator<Item = T>> {
+    left: Peekable<L>,
+    right: Peekable<R>,
+}
+impl<T, L: Iterator<Item = T>, R: Iterator<Item = T>> Merge<T, L, R> {
+    /// Merge two sorted iterators.
+    pub(crate) fn new<A, B>(left: A, right: B) -> Self
+    where
+        A: IntoIterator<IntoIter = L>,
+        B: IntoIterator<IntoIter = R>,
+    {
+        Self {
+            left: left.into_iter().peekable(),
+            right: right.into_iter().peekable(),
+        }
+    }
+}
+impl<T: Ord, L: Iterator<Item = T>, R: Iterator<Item = T>> Iterator for Merge<T, L, R> {
+    type Item = (T, Side);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.left.peek(), self.right.peek()) {
+            (Some(left), Some(right)) if right < left => {
+                self.right.next().map(|right| (right, Side::Right))
+            }
+            (Some(_), _) => self.left.next().map(|left| (left, Side::Left)),
+            (None, _) => self.right.next().map(|right| (right, Side::Right)),
+        }
+    }
+}
@@ -0,0 +1,511 @@
+//! Fixed slots, that may have holes.
+//!
+//! The filled elements of a [`PartialArray`] are always the contiguous prefix
+//! `0..len`. This module provides the [`SparseArray`], which instead tracks the
+//! occupancy of each of its `N` slots. This allows tables indexed e.g. by a
+//! channel number, which do not have a value for every index:
+//! ```
+//! use partial_array::sparse::SparseArray;
+//!
+//! let mut gains = SparseArray::<f32, 16>::new();
+//! gains.set(3, 0.5);
+//! gains.set(11, 2.0);
+//!
+//! assert_eq!(gains.get(3), Some(&0.5));
+//! assert_eq!(gains.get(4), None);
+//! let channels: Vec<_> = gains.iter().map(|(channel, _)| channel).collect();
+//! assert_eq!(channels, [3, 11]);
+//! ```
+//!
+//! [`PartialArray`]: crate::PartialArray
+use crate::PartialArray;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{Enumerate, FromIterator, FusedIterator};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// A word of the occupancy bitmap of a [`SparseArray`].
+type Word = u32;
+
+/// The number of slots, whose occupancy is stored in a single [`Word`].
+const WORD_BITS: usize = Word::BITS as usize;
+
+/// Check, whether the slot at `index` is marked as occupied in the bitmap.
+fn is_occupied(occupied: &[Word], index: usize) -> bool {
+    occupied[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+}
+
+/// An array of `N` slots, each of which may or may not contain a value.
+///
+/// Accessing a slot takes O(1) time, iterating takes O(N) time regardless of
+/// the number of values. The occupancy is stored as a bitmap of `W` words of 32
+/// bits each, so `N` must not exceed `32 * W`, which is checked at compile time.
+/// As the number of words cannot be computed from `N` on stable Rust, it is a
+/// separate parameter, which defaults to a single word (i.e. up to 32 slots):
+/// ```
+/// # use partial_array::sparse::SparseArray;
+/// let small = SparseArray::<u8, 16>::new();
+/// let large = SparseArray::<u8, 100, 4>::new();
+/// ```
+/// A bitmap, which is too small, fails to compile:
+/// ```compile_fail
+/// # use partial_array::sparse::SparseArray;
+/// let too_large = SparseArray::<u8, 33>::new();
+/// ```
+pub struct SparseArray<T, const N: usize, const W: usize = 1> {
+    /// The number of occupied slots.
+    len: usize,
+    /// Whether each slot is occupied, i.e. the slot `i` is occupied, if the bit
+    /// `i % 32` of the word `i / 32` is set.
+    ///
+    /// The value of each occupied slot must be initialized.
+    occupied: [Word; W],
+    /// The storage for the values.
+    values: [MaybeUninit<T>; N],
+}
+impl<T, const N: usize, const W: usize> SparseArray<T, N, W> {
+    /// Required for `MaybeUninit::uninit()` in array initializers
+    const UNINIT: MaybeUninit<T> = MaybeUninit::uninit();
+
+    /// Fails the compilation, if the bitmap has not enough bits for `N` slots.
+    const BITMAP_FITS: () = assert!(
+        N <= W * WORD_BITS,
+        "the occupancy bitmap needs `W` >= `N` / 32 (rounded up) words"
+    );
+
+    /// Create a [`SparseArray`] with only vacant slots.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)] // evaluates the compile-time check
+        let () = Self::BITMAP_FITS;
+        Self {
+            len: 0,
+            occupied: [0; W],
+            values: [Self::UNINIT; N],
+        }
+    }
+
+    /// Mark the slot at `index` (which must be in bounds) as occupied or not.
+    fn set_occupied(&mut self, index: usize, occupied: bool) {
+        let word = &mut self.occupied[index / WORD_BITS];
+        let bit = 1 << (index % WORD_BITS);
+        if occupied {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    /// The number of occupied slots.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check, whether all slots are vacant.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of slots (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check, whether the slot at `index` is occupied.
+    ///
+    /// This returns `false` for out-of-bounds indices.
+    pub fn contains(&self, index: usize) -> bool {
+        index < N && is_occupied(&self.occupied, index)
+    }
+
+    /// Get a reference to the value at `index` or [`None`], if the slot is
+    /// vacant or out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if !self.contains(index) {
+            return None;
+        }
+        // SAFETY: the slot is occupied and therefore initialized.
+        Some(unsafe { self.values[index].assume_init_ref() })
+    }
+
+    /// Get a mutable reference to the value at `index` or [`None`], if the
+    /// slot is vacant or out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if !self.contains(index) {
+            return None;
+        }
+        // SAFETY: the slot is occupied and therefore initialized.
+        Some(unsafe { self.values[index].assume_init_mut() })
+    }
+
+    /// Store a value at `index` and return the previous value, if any.
+    ///
+    /// # Panics
+    /// Panics, if `index` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sparse::SparseArray;
+    /// let mut array = SparseArray::<char, 4>::new();
+    ///
+    /// assert_eq!(array.set(2, 'a'), None);
+    /// assert_eq!(array.set(2, 'b'), Some('a'));
+    /// assert_eq!(array.len(), 1);
+    /// ```
+    pub fn set(&mut self, index: usize, value: T) -> Option<T> {
+        assert!(index < N, "index out of bounds");
+
+        let previous = self.take(index);
+        self.values[index] = MaybeUninit::new(value);
+        self.set_occupied(index, true);
+        self.len += 1;
+        previous
+    }
+
+    /// Remove the value at `index` and return it or [`None`], if the slot is
+    /// vacant or out of bounds.
+    pub fn take(&mut self, index: usize) -> Option<T> {
+        if !self.contains(index) {
+            return None;
+        }
+
+        let value = mem::replace(&mut self.values[index], Self::UNINIT);
+        self.set_occupied(index, false);
+        self.len -= 1;
+        // SAFETY: the slot was occupied and is vacant now, so the value is
+        // initialized and not read or dropped again.
+        Some(unsafe { value.assume_init() })
+    }
+
+    /// Get a mutable reference to the value at `index`, storing the result of
+    /// `f` first, if the slot is vacant.
+    ///
+    /// # Panics
+    /// Panics, if `index` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sparse::SparseArray;
+    /// let mut counts = SparseArray::<u32, 8>::new();
+    /// for channel in [1, 5, 1] {
+    ///     *counts.get_or_insert_with(channel, || 0) += 1;
+    /// }
+    /// assert_eq!(counts.get(1), Some(&2));
+    /// assert_eq!(counts.get(5), Some(&1));
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&mut self, index: usize, f: F) -> &mut T {
+        if !self.contains(index) {
+            self.set(index, f());
+        }
+        // SAFETY: the slot is occupied (either before or by the `set()` above)
+        // and therefore initialized.
+        unsafe { self.values[index].assume_init_mut() }
+    }
+
+    /// Remove and drop all values.
+    pub fn clear(&mut self) {
+        (0..N).for_each(|index| drop(self.take(index)));
+    }
+
+    /// Iterate over the indices and values of the occupied slots in ascending
+    /// order of the indices.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            occupied: &self.occupied,
+            slots: self.values.iter().enumerate(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterate over the indices and mutable references to the values of the
+    /// occupied slots in ascending order of the indices.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            occupied: &self.occupied,
+            slots: self.values.iter_mut().enumerate(),
+            remaining: self.len,
+        }
+    }
+
+    /// Convert into a [`PartialArray`] of the values in ascending order of
+    /// their indices, i.e. the holes are removed.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::sparse::SparseArray;
+    /// let array: SparseArray<char, 8> = [(6, 'b'), (2, 'a')].iter().copied().collect();
+    /// assert_eq!(array.into_partial_array(), ['a', 'b']);
+    /// ```
+    pub fn into_partial_array(self) -> PartialArray<T, N> {
+        self.into_iter().map(|(_, value)| value).collect()
+    }
+}
+impl<T, const N: usize, const W: usize> Drop for SparseArray<T, N, W> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+impl<T, const N: usize, const W: usize> Default for SparseArray<T, N, W> {
+    /// Initialize a [`SparseArray`] with only vacant slots.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Clone, const N: usize, const W: usize> Clone for SparseArray<T, N, W> {
+    fn clone(&self) -> Self {
+        self.iter()
+            .map(|(index, value)| (index, value.clone()))
+            .collect()
+    }
+}
+impl<T: Debug, const N: usize, const W: usize> Debug for SparseArray<T, N, W> {
+    /// Debug-format the occupied slots as a map from the indices to the values.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<T: PartialEq, const N: usize, const W: usize> PartialEq for SparseArray<T, N, W> {
+    /// Compare the occupancy and the values of the occupied slots.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+impl<T: Eq, const N: usize, const W: usize> Eq for SparseArray<T, N, W> {}
+impl<T, const N: usize, const W: usize> Index<usize> for SparseArray<T, N, W> {
+    type Output = T;
+
+    /// Get a reference to the value at `index`.
+    ///
+    /// # Panics
+    /// Panics, if the slot is vacant or out of bounds.
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("slot is vacant or out of bounds")
+    }
+}
+impl<T, const N: usize, const W: usize> IndexMut<usize> for SparseArray<T, N, W> {
+    /// Get a mutable reference to the value at `index`.
+    ///
+    /// # Panics
+    /// Panics, if the slot is vacant or out of bounds.
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index)
+            .expect("slot is vacant or out of bounds")
+    }
+}
+impl<T, const N: usize, const W: usize> FromIterator<(usize, T)> for SparseArray<T, N, W> {
+    /// Build up a [`SparseArray`] from an iterator of indices and values.
+    ///
+    /// Later values replace earlier ones with the same index.
+    ///
+    /// # Panics
+    /// Panics, if an index is out of bounds.
+    fn from_iter<I: IntoIterator<Item = (usize, T)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+impl<T, const N: usize, const W: usize> Extend<(usize, T)> for SparseArray<T, N, W> {
+    /// Store the values of the iterator at their indices.
+    ///
+    /// # Panics
+    /// Panics, if an index is out of bounds.
+    fn extend<I: IntoIterator<Item = (usize, T)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(index, value)| {
+            self.set(index, value);
+        });
+    }
+}
+impl<T, const N: usize, const W: usize> From<SparseArray<T, N, W>> for PartialArray<T, N> {
+    /// Get the values in ascending order of their indices.
+    fn from(array: SparseArray<T, N, W>) -> Self {
+        array.into_partial_array()
+    }
+}
+impl<T, const N: usize, const W: usize> IntoIterator for SparseArray<T, N, W> {
+    type Item = (usize, T);
+    type IntoIter = IntoIter<T, N, W>;
+
+    /// Iterate over the indices and values of the occupied slots by value.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            array: self,
+            front: 0,
+            back: N,
+        }
+    }
+}
+impl<'a, T, const N: usize, const W: usize> IntoIterator for &'a SparseArray<T, N, W> {
+    type Item = (usize, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const N: usize, const W: usize> IntoIterator for &'a mut SparseArray<T, N, W> {
+    type Item = (usize, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the occupied slots of a [`SparseArray`].
+///
+/// This struct is created by the [`iter`] method.
+///
+/// [`iter`]: SparseArray::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T> {
+    /// The occupancy bitmap of the array.
+    occupied: &'a [Word],
+    /// The remaining slots.
+    slots: Enumerate<slice::Iter<'a, MaybeUninit<T>>>,
+    /// The number of occupied slots left.
+    remaining: usize,
+}
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let occupied = self.occupied;
+        let (index, value) = self
+            .slots
+            .find(|(index, _)| is_occupied(occupied, *index))?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied and therefore initialized.
+        Some((index, unsafe { value.assume_init_ref() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let occupied = self.occupied;
+        let (index, value) = self
+            .slots
+            .rfind(|(index, _)| is_occupied(occupied, *index))?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied and therefore initialized.
+        Some((index, unsafe { value.assume_init_ref() }))
+    }
+}
+impl<T> FusedIterator for Iter<'_, T> {}
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A mutable iterator over the occupied slots of a [`SparseArray`].
+///
+/// This struct is created by the [`iter_mut`] method.
+///
+/// [`iter_mut`]: SparseArray::iter_mut
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, T> {
+    /// The occupancy bitmap of the array.
+    occupied: &'a [Word],
+    /// The remaining slots.
+    slots: Enumerate<slice::IterMut<'a, MaybeUninit<T>>>,
+    /// The number of occupied slots left.
+    remaining: usize,
+}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let occupied = self.occupied;
+        let (index, value) = self
+            .slots
+            .find(|(index, _)| is_occupied(occupied, *index))?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied and therefore initialized.
+        Some((index, unsafe { value.assume_init_mut() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let occupied = self.occupied;
+        let (index, value) = self
+            .slots
+            .rfind(|(index, _)| is_occupied(occupied, *index))?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied and therefore initialized.
+        Some((index, unsafe { value.assume_init_mut() }))
+    }
+}
+impl<T> FusedIterator for IterMut<'_, T> {}
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("IterMut")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator that moves out of a [`SparseArray`].
+///
+/// This struct is created by the [`into_iter`] method on [`SparseArray`]
+/// (provided by the [`IntoIterator`] trait).
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter<T, const N: usize, const W: usize = 1> {
+    /// The array, whose remaining slots are `front..back`.
+    ///
+    /// Values are taken out of the array, so the slots before `front` and
+    /// after `back` are vacant.
+    array: SparseArray<T, N, W>,
+    front: usize,
+    back: usize,
+}
+impl<T: Debug, const N: usize, const W: usize> Debug for IntoIter<T, N, W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.array).finish()
+    }
+}
+impl<T, const N: usize, const W: usize> Iterator for IntoIter<T, N, W> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let index = self.front;
+            self.front += 1;
+            if let Some(value) = self.array.take(index) {
+                return Some((index, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.array.len(), Some(self.array.len()))
+    }
+}
+impl<T, const N: usize, const W: usize> DoubleEndedIterator for IntoIter<T, N, W> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            self.back -= 1;
+            let index = self.back;
+            if let Some(value) = self.array.take(index) {
+                return Some((index, value));
+            }
+        }
+        None
+    }
+}
+impl<T, const N: usize, const W: usize> FusedIterator for IntoIter<T, N, W> {}
+impl<T, const N: usize, const W: usize> ExactSizeIterator for IntoIter<T, N, W> {}
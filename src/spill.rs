@@ -0,0 +1,255 @@
+//! Partial arrays, that spill over to the heap instead of panicking.
+//!
+//! This module is only available with the `alloc` feature. A [`PartialArray`]
+//! panics, if it is filled with more than `N` elements. This is desired on
+//! embedded targets, but on the host side an outlier should rather cost an
+//! allocation than a panic. Therefore this module provides the [`SpillArray`],
+//! which stores up to `N` elements inline and transparently moves them into a
+//! [`Vec`] once more elements are added:
+//! ```
+//! # use partial_array::spill::SpillArray;
+//! let small: SpillArray<u32, 4> = (0..3).collect();
+//! assert!(!small.is_spilled());
+//!
+//! let large: SpillArray<u32, 4> = (0..42).collect();
+//! assert!(large.is_spilled());
+//! assert_eq!(large.len(), 42);
+//! ```
+use crate::PartialArray;
+use alloc::vec::{self, Vec};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{FromIterator, FusedIterator};
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+
+/// A potentially partially filled array, that spills over to the heap.
+///
+/// Up to `N` elements are stored inline in a [`PartialArray`]. As soon as more
+/// elements are added, all elements are moved into a [`Vec`]. The storage is
+/// never moved back inline, even if elements are removed later on. Regardless
+/// of the storage, it [deref]s to a slice of the elements.
+///
+/// [deref]: core::ops::Deref::deref
+#[derive(Clone)]
+pub enum SpillArray<T, const N: usize> {
+    /// The elements are stored inline (at most `N` elements).
+    Inline(PartialArray<T, N>),
+    /// The elements are stored on the heap.
+    Heap(Vec<T>),
+}
+impl<T, const N: usize> SpillArray<T, N> {
+    /// Check, whether the elements are stored on the heap.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::Heap(_))
+    }
+
+    /// Append an element to the back, spilling to the heap if necessary.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::spill::SpillArray;
+    /// let mut array = SpillArray::<_, 1>::default();
+    /// array.push(1);
+    /// assert!(!array.is_spilled());
+    /// array.push(2);
+    /// assert!(array.is_spilled());
+    /// assert_eq!(array, [1, 2]);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        self.extend(Some(value));
+    }
+
+    /// Remove the last element and return it or [`None`], if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        match self {
+            Self::Inline(array) => array.pop(),
+            Self::Heap(vec) => vec.pop(),
+        }
+    }
+
+    /// Convert into a [`Vec`], which does not allocate, if already spilled.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::Inline(array) => array.into_iter().collect(),
+            Self::Heap(vec) => vec,
+        }
+    }
+
+    /// Move the elements to the heap (if not yet done) and return the [`Vec`].
+    ///
+    /// The [`Vec`] has space for at least `additional` more elements.
+    fn spill(&mut self, additional: usize) -> &mut Vec<T> {
+        if let Self::Inline(array) = self {
+            let mut vec = Vec::with_capacity(array.len() + additional);
+            vec.extend(mem::take(array));
+            *self = Self::Heap(vec);
+        }
+        match self {
+            Self::Heap(vec) => vec,
+            Self::Inline(_) => unreachable!("storage was spilled above"),
+        }
+    }
+}
+impl<T, const N: usize> Default for SpillArray<T, N> {
+    /// Initialize an empty, inline [`SpillArray`].
+    fn default() -> Self {
+        Self::Inline(PartialArray::default())
+    }
+}
+impl<T, const N: usize> Deref for SpillArray<T, N> {
+    type Target = [T];
+
+    /// Dereference to the slice of elements (regardless of the storage).
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Inline(array) => array,
+            Self::Heap(vec) => vec,
+        }
+    }
+}
+impl<T, const N: usize> DerefMut for SpillArray<T, N> {
+    /// Dereference to the slice of elements (regardless of the storage).
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Inline(array) => array,
+            Self::Heap(vec) => vec,
+        }
+    }
+}
+impl<T: Debug, const N: usize> Debug for SpillArray<T, N> {
+    /// Debug-format the slice of elements (regardless of the storage).
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<SpillArray<T, M>>
+    for SpillArray<T, N>
+{
+    /// Compare the elements of [`SpillArray`]s (regardless of the storage).
+    fn eq(&self, other: &SpillArray<T, M>) -> bool {
+        self.deref() == other.deref()
+    }
+}
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<[T; M]> for SpillArray<T, N> {
+    /// Compare a [`SpillArray`] with a normal array.
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.deref() == &other[..]
+    }
+}
+impl<T: Eq, const N: usize> Eq for SpillArray<T, N> {}
+impl<T, const N: usize> From<PartialArray<T, N>> for SpillArray<T, N> {
+    fn from(array: PartialArray<T, N>) -> Self {
+        Self::Inline(array)
+    }
+}
+impl<T, const N: usize> From<Vec<T>> for SpillArray<T, N> {
+    /// Wrap a [`Vec`], i.e. the elements are (and stay) on the heap.
+    fn from(vec: Vec<T>) -> Self {
+        Self::Heap(vec)
+    }
+}
+impl<T, const N: usize> FromIterator<T> for SpillArray<T, N> {
+    /// Build up a [`SpillArray`] from an iterator.
+    ///
+    /// This spills to the heap, if the iterator has more than `N` elements.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::default();
+        result.extend(iter);
+        result
+    }
+}
+impl<T, const N: usize> Extend<T> for SpillArray<T, N> {
+    /// Append the elements of the iterator.
+    ///
+    /// Inline storage is filled up first. If there are still elements left, all
+    /// elements are moved to the heap and the rest is appended there.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        if let Self::Inline(array) = self {
+            let remaining = N - array.len();
+            array.extend(iter.by_ref().take(remaining));
+        }
+
+        let mut iter = iter.peekable();
+        if iter.peek().is_some() {
+            let (additional, _) = iter.size_hint();
+            self.spill(additional).extend(iter);
+        }
+    }
+}
+impl<T, const N: usize> IntoIterator for SpillArray<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(match self {
+            Self::Inline(array) => IntoIterInner::Inline(array.into_iter()),
+            Self::Heap(vec) => IntoIterInner::Heap(vec.into_iter()),
+        })
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a SpillArray<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a mut SpillArray<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator that moves out of a [`SpillArray`].
+///
+/// This struct is created by the [`into_iter`] method on [`SpillArray`]
+/// (provided by the [`IntoIterator`] trait).
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct IntoIter<T, const N: usize>(IntoIterInner<T, N>);
+enum IntoIterInner<T, const N: usize> {
+    Inline(crate::iter::IntoIter<T, N>),
+    Heap(vec::IntoIter<T>),
+}
+impl<T: Debug, const N: usize> Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.0 {
+            IntoIterInner::Inline(iter) => Debug::fmt(iter, f),
+            IntoIterInner::Heap(iter) => f.debug_list().entries(iter.as_slice()).finish(),
+        }
+    }
+}
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IntoIterInner::Inline(iter) => iter.next(),
+            IntoIterInner::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IntoIterInner::Inline(iter) => iter.size_hint(),
+            IntoIterInner::Heap(iter) => iter.size_hint(),
+        }
+    }
+}
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IntoIterInner::Inline(iter) => iter.next_back(),
+            IntoIterInner::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
@@ -0,0 +1,306 @@
+//! A bounded single-producer single-consumer queue.
+//!
+//! This module is only available on targets supporting atomic operations on
+//! pointer-sized integers. Like the [`IntoIter`] of a [`PartialArray`], the
+//! [`Queue`] tracks a window of initialized elements inside inline storage for
+//! `N` elements, but the window wraps around, so that it can be used as a
+//! first-in-first-out queue indefinitely.
+//!
+//! A [`Queue`] can be [`split`] into a [`Producer`] and a [`Consumer`], which
+//! can be used from different threads or contexts (e.g. an interrupt handler
+//! and a task) without locking:
+//! ```
+//! # use partial_array::spsc::Queue;
+//! let mut queue = Queue::<u32, 4>::new();
+//! let (mut producer, mut consumer) = queue.split();
+//!
+//! std::thread::scope(|scope| {
+//!     scope.spawn(move || {
+//!         for i in 0..10 {
+//!             while producer.enqueue(i).is_err() {}
+//!         }
+//!     });
+//!
+//!     for i in 0..10 {
+//!         let value = loop {
+//!             if let Some(value) = consumer.dequeue() {
+//!                 break value;
+//!             }
+//!         };
+//!         assert_eq!(value, i);
+//!     }
+//! });
+//! ```
+//!
+//! [`IntoIter`]: crate::iter::IntoIter
+//! [`PartialArray`]: crate::PartialArray
+//! [`split`]: Queue::split
+use crate::sync::{AtomicUsize, Ordering, UnsafeCell};
+use crate::CapacityError;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// A bounded single-producer single-consumer queue for up to `N` elements.
+///
+/// With exclusive access, elements can be added and removed directly. For
+/// concurrent use, the queue is [split] into a [`Producer`] and a [`Consumer`].
+///
+/// [split]: Queue::split
+pub struct Queue<T, const N: usize> {
+    /// The number of dequeued elements modulo `2 * N`, only written by the
+    /// consumer.
+    head: AtomicUsize,
+    /// The number of enqueued elements modulo `2 * N`, only written by the
+    /// producer.
+    ///
+    /// The elements with the (wrapping) indices `head..tail` modulo `N` are
+    /// initialized. Counting modulo `2 * N` instead of `N` distinguishes a full
+    /// from an empty queue.
+    tail: AtomicUsize,
+    /// The storage for the items.
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+// SAFETY: the producer moves elements into the queue and the consumer moves
+// them out (potentially on different threads), so `Send` is required. Both only
+// access the slots, which are assigned to them by the synchronized indices.
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+impl<T, const N: usize> Queue<T, N> {
+    /// Required for `UnsafeCell::new(MaybeUninit::uninit())` in array
+    /// initializers
+    #[cfg(not(loom))]
+    #[allow(clippy::declare_interior_mutable_const)] // only used to initialize
+    const EMPTY: UnsafeCell<MaybeUninit<T>> = UnsafeCell::new(MaybeUninit::uninit());
+
+    /// Create an empty [`Queue`].
+    ///
+    /// This is a `const fn`, so the queue can be placed in a `static`.
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            buffer: [Self::EMPTY; N],
+        }
+    }
+
+    /// Create an empty [`Queue`].
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            buffer: core::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+        }
+    }
+
+    /// The maximum number of elements (`N`).
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The number of elements in the queue.
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        Self::distance(head, tail)
+    }
+
+    /// Check, whether there are no elements in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check, whether no more elements can be enqueued.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Add an element to the back or return it, if the queue is full.
+    pub fn enqueue(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        // SAFETY: the exclusive reference guarantees a single producer
+        unsafe { self.enqueue_unchecked(value) }
+    }
+
+    /// Remove the element at the front or return [`None`], if it is empty.
+    pub fn dequeue(&mut self) -> Option<T> {
+        // SAFETY: the exclusive reference guarantees a single consumer
+        unsafe { self.dequeue_unchecked() }
+    }
+
+    /// Split the queue into a [`Producer`] and a [`Consumer`].
+    ///
+    /// Both halves can be used concurrently, e.g. from different threads.
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+
+    /// The number of elements between the counters `head` and `tail`.
+    fn distance(head: usize, tail: usize) -> usize {
+        if head <= tail {
+            tail - head
+        } else {
+            tail + 2 * N - head
+        }
+    }
+
+    /// Advance a counter by `n` (modulo `2 * N`).
+    fn advance(index: usize, n: usize) -> usize {
+        let index = index + n;
+        if index >= 2 * N {
+            index - 2 * N
+        } else {
+            index
+        }
+    }
+
+    /// Add an element to the back or return it, if the queue is full.
+    ///
+    /// # Safety
+    /// There must be no concurrent call of this function (single producer).
+    pub(crate) unsafe fn enqueue_unchecked(&self, value: T) -> Result<(), CapacityError<T>> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        // synchronizes with the consumer, which has moved out of the slot
+        let head = self.head.load(Ordering::Acquire);
+        if Self::distance(head, tail) == N {
+            return Err(CapacityError::new(value));
+        }
+
+        self.buffer[tail % N].with_mut(|slot| {
+            // SAFETY: the slot is outside of the initialized window, so the
+            // consumer does not access it until `tail` is incremented.
+            ptr::write(slot, MaybeUninit::new(value))
+        });
+        self.tail.store(Self::advance(tail, 1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Remove the element at the front or return [`None`], if it is empty.
+    ///
+    /// # Safety
+    /// There must be no concurrent call of this function (single consumer).
+    pub(crate) unsafe fn dequeue_unchecked(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        // synchronizes with the producer, which has written the slot
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        let value = self.buffer[head % N].with(|slot| {
+            // SAFETY: the slot is inside the initialized window, so it is
+            // initialized and the producer does not access it until `head` is
+            // incremented, which also prevents reading it again.
+            ptr::read(slot).assume_init()
+        });
+        self.head.store(Self::advance(head, 1), Ordering::Release);
+        Some(value)
+    }
+}
+impl<T, const N: usize> Default for Queue<T, N> {
+    /// Initialize an empty [`Queue`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Debug, const N: usize> Debug for Queue<T, N> {
+    /// Debug-format the elements from front to back.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let head = self.head.load(Ordering::Acquire);
+        let len = self.len();
+        let mut list = f.debug_list();
+        for i in 0..len {
+            self.buffer[Self::advance(head, i) % N].with(|slot| {
+                // SAFETY: the slot is inside the initialized window and the
+                // queue is not split, as it is borrowed here.
+                list.entry(unsafe { (*slot).assume_init_ref() });
+            });
+        }
+        list.finish()
+    }
+}
+impl<T, const N: usize> Drop for Queue<T, N> {
+    fn drop(&mut self) {
+        while self.dequeue().is_some() {}
+    }
+}
+
+/// The producing half of a [split] [`Queue`].
+///
+/// [split]: Queue::split
+pub struct Producer<'a, T, const N: usize> {
+    queue: &'a Queue<T, N>,
+}
+impl<T, const N: usize> Producer<'_, T, N> {
+    /// Add an element to the back or return it, if the queue is full.
+    pub fn enqueue(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        // SAFETY: there is only a single producer per queue, as `split()`
+        // borrows the queue mutably. It is borrowed mutably here.
+        unsafe { self.queue.enqueue_unchecked(value) }
+    }
+
+    /// Check, whether no more elements can be enqueued.
+    ///
+    /// The result may be outdated immediately, as the consumer concurrently
+    /// removes elements.
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    /// The number of elements in the queue.
+    ///
+    /// The result may be outdated immediately, as the consumer concurrently
+    /// removes elements.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check, whether there are no elements in the queue.
+    ///
+    /// The result may be outdated immediately, as the consumer concurrently
+    /// removes elements.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+impl<T, const N: usize> Debug for Producer<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Producer").finish_non_exhaustive()
+    }
+}
+
+/// The consuming half of a [split] [`Queue`].
+///
+/// [split]: Queue::split
+pub struct Consumer<'a, T, const N: usize> {
+    queue: &'a Queue<T, N>,
+}
+impl<T, const N: usize> Consumer<'_, T, N> {
+    /// Remove the element at the front or return [`None`], if it is empty.
+    pub fn dequeue(&mut self) -> Option<T> {
+        // SAFETY: there is only a single consumer per queue, as `split()`
+        // borrows the queue mutably. It is borrowed mutably here.
+        unsafe { self.queue.dequeue_unchecked() }
+    }
+
+    /// The number of elements in the queue.
+    ///
+    /// The result may be outdated immediately, as the producer concurrently
+    /// adds elements.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check, whether there are no elements in the queue.
+    ///
+    /// The result may be outdated immediately, as the producer concurrently
+    /// adds elements.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+impl<T, const N: usize> Debug for Consumer<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Consumer").finish_non_exhaustive()
+    }
+}
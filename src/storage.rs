@@ -0,0 +1,128 @@
+//! The shared logic of partially filled storage.
+//!
+//! [`PartialArray`], [`PartialSlice`] and [`PartialBuf`] all consist of a
+//! counter `filled` and some storage of [`MaybeUninit`] entries, of which the
+//! prefix `0..filled` is initialized. The functions of this module implement
+//! the operations on such a pair, so that the types only differ in where the
+//! storage lives.
+//!
+//! [`PartialArray`]: crate::PartialArray
+//! [`PartialSlice`]: crate::slice::PartialSlice
+//! [`PartialBuf`]: crate::buf::PartialBuf
+use crate::CapacityError;
+use core::mem::{self, MaybeUninit};
+
+/// Append an element behind the filled entries or return it, if the storage is
+/// full.
+pub(crate) fn try_push<T>(
+    filled: &mut usize,
+    storage: &mut [MaybeUninit<T>],
+    value: T,
+) -> Result<(), CapacityError<T>> {
+    match storage.get_mut(*filled) {
+        Some(entry) => {
+            *entry = MaybeUninit::new(value);
+            *filled += 1;
+            Ok(())
+        }
+        None => Err(CapacityError::new(value)),
+    }
+}
+
+/// Remove the last filled entry and return it or [`None`], if it is empty.
+///
+/// # Safety
+/// The entries `0..*filled` of `storage` must be initialized.
+pub(crate) unsafe fn pop<T>(filled: &mut usize, storage: &mut [MaybeUninit<T>]) -> Option<T> {
+    if *filled == 0 {
+        return None;
+    }
+
+    *filled -= 1;
+    let value = mem::replace(&mut storage[*filled], MaybeUninit::uninit());
+    // SAFETY: the entry was filled before decrementing the counter, so it is
+    // initialized. It is not part of the filled entries anymore, so it is not
+    // read or dropped again.
+    Some(value.assume_init())
+}
+
+/// Shorten the filled entries to the first `len` and drop the rest.
+///
+/// # Safety
+/// The entries `0..*filled` of `storage` must be initialized.
+pub(crate) unsafe fn truncate<T>(filled: &mut usize, storage: &mut [MaybeUninit<T>], len: usize) {
+    let end = *filled;
+    if len >= end {
+        return;
+    }
+
+    // shorten first, so that a panicking destructor leaks the remaining
+    // elements instead of dropping them twice
+    *filled = len;
+    (len..end)
+        .map(|i| mem::replace(&mut storage[i], MaybeUninit::uninit()))
+        .map(|entry| {
+            // SAFETY: we only iterate over the previously filled entries, so we
+            // can be sure, that this is initialized.
+            entry.assume_init()
+        })
+        .for_each(drop);
+}
+
+/// Append the elements of the iterator behind the filled entries.
+///
+/// # Panics
+/// Panics, if the iterator has more elements than there is space left.
+pub(crate) fn extend<T, I: IntoIterator<Item = T>>(
+    filled: &mut usize,
+    storage: &mut [MaybeUninit<T>],
+    iter: I,
+) {
+    let remaining = storage.len() - *filled;
+    extend_with(remaining, iter, |element| {
+        storage[*filled] = MaybeUninit::new(element);
+        *filled += 1;
+    });
+}
+
+/// Pass the elements of the iterator to `add`, as long as there is space left.
+///
+/// This is the shared implementation of [`Extend`] for collections, which add
+/// elements in their own way (e.g. at the sorted position).
+///
+/// # Panics
+/// Panics, if the iterator has more than `remaining` elements.
+pub(crate) fn extend_with<T, I, F>(remaining: usize, iter: I, add: F)
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T),
+{
+    let mut iter = iter.into_iter();
+    iter.by_ref().take(remaining).for_each(add);
+
+    // check, that there are no more elements left
+    let remaining = iter.count();
+    assert_eq!(remaining, 0, "Iterator has {} elements to much", remaining);
+}
+
+/// View the filled entries as a slice.
+///
+/// # Safety
+/// The entries `0..filled` of `storage` must be initialized.
+pub(crate) unsafe fn initialized<T>(storage: &[MaybeUninit<T>], filled: usize) -> &[T] {
+    let slice = &storage[..filled];
+    // SAFETY: the caller guarantees, that `0..filled` is initialized, so it is
+    // no UB reading those. The transmute itself is safe, since `MaybeUninit` is
+    // `#[repr(transparent)]`.
+    mem::transmute::<&[MaybeUninit<T>], &[T]>(slice)
+}
+
+/// View the filled entries as a mutable slice.
+///
+/// # Safety
+/// The entries `0..filled` of `storage` must be initialized.
+pub(crate) unsafe fn initialized_mut<T>(storage: &mut [MaybeUninit<T>], filled: usize) -> &mut [T] {
+    let slice = &mut storage[..filled];
+    // SAFETY: see `initialized()`
+    mem::transmute::<&mut [MaybeUninit<T>], &mut [T]>(slice)
+}
@@ -0,0 +1,99 @@
+//! Collecting asynchronous streams into [`PartialArray`]s.
+//!
+//! This module is only available with the `futures` feature. As a
+//! [`PartialArray`] implements [`Default`] and [`Extend`], it can be used as the
+//! target of [`StreamExt::collect`] directly. This panics, if the stream yields
+//! more than `N` items, though. Therefore this module provides the
+//! [`PartialStreamExt::collect_partial`] method, that stops polling the stream
+//! once the array is full:
+//! ```
+//! # use partial_array::PartialArray;
+//! use partial_array::stream::PartialStreamExt;
+//!
+//! # futures::executor::block_on(async {
+//! let responses = futures::stream::iter(1..);
+//! let first: PartialArray<u32, 4> = responses.collect_partial::<4>().await;
+//! assert_eq!(first, [1, 2, 3, 4]);
+//! # });
+//! ```
+//!
+//! [`StreamExt::collect`]: https://docs.rs/futures/0.3/futures/stream/trait.StreamExt.html#method.collect
+use crate::PartialArray;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::{FusedFuture, Stream};
+
+/// An extension trait for [`Stream`]s to collect them into [`PartialArray`]s.
+pub trait PartialStreamExt: Stream {
+    /// Collect up to `N` items of the stream into a [`PartialArray`].
+    ///
+    /// The returned future resolves, once the stream is exhausted or `N` items
+    /// are collected. In the latter case, the stream is not polled any further,
+    /// i.e. this never panics because of too many items.
+    fn collect_partial<const N: usize>(self) -> CollectPartial<Self, N>
+    where
+        Self: Sized,
+    {
+        CollectPartial {
+            stream: self,
+            array: PartialArray::default(),
+            done: false,
+        }
+    }
+}
+impl<S: Stream + ?Sized> PartialStreamExt for S {}
+
+/// A future collecting up to `N` items of a stream into a [`PartialArray`].
+///
+/// This struct is created by the [`collect_partial`] method on
+/// [`PartialStreamExt`].
+///
+/// [`collect_partial`]: PartialStreamExt::collect_partial
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CollectPartial<S: Stream, const N: usize> {
+    stream: S,
+    array: PartialArray<S::Item, N>,
+    done: bool,
+}
+impl<S: Stream, const N: usize> Future for CollectPartial<S, N> {
+    type Output = PartialArray<S::Item, N>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the stream is pinned structurally: it is never moved out of
+        // the pinned future. The other fields are not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        assert!(!this.done, "`CollectPartial` polled after completion");
+        while this.array.len() < N {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => this.array.extend(Some(item)),
+                Poll::Ready(None) => break,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        this.done = true;
+        Poll::Ready(mem::take(&mut this.array))
+    }
+}
+impl<S: Stream, const N: usize> FusedFuture for CollectPartial<S, N> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+impl<S, const N: usize> Debug for CollectPartial<S, N>
+where
+    S: Stream + Debug,
+    S::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CollectPartial")
+            .field("stream", &self.stream)
+            .field("array", &self.array)
+            .finish()
+    }
+}
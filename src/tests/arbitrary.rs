@@ -0,0 +1,26 @@
+use crate::PartialArray;
+use arbitrary::{Arbitrary, Unstructured};
+
+#[test]
+fn empty_input() {
+    let mut data = Unstructured::new(&[]);
+    let array = PartialArray::<u32, 4>::arbitrary(&mut data).unwrap();
+    assert!(array.is_empty());
+}
+
+#[test]
+fn full() {
+    let mut data = Unstructured::new(&[1, 1, 2, 3, 4]);
+    let array = PartialArray::<u8, 4>::arbitrary(&mut data).unwrap();
+    assert_eq!(array, [1, 2, 3, 4]);
+}
+
+#[test]
+fn bounded_length() {
+    let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let mut data = Unstructured::new(&data);
+    for _ in 0..100 {
+        let array = PartialArray::<u8, 7>::arbitrary(&mut data).unwrap();
+        assert!(array.len() <= 7);
+    }
+}
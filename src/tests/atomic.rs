@@ -0,0 +1,136 @@
+//! The `loom` tests are only compiled with `--cfg loom` and check all possible
+//! interleavings, e.g. via:
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --lib tests::atomic
+//! ```
+use crate::atomic::AtomicPartialArray;
+
+#[cfg(not(loom))]
+mod threads {
+    use super::*;
+    use crate::tests::DropCounter;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    #[test]
+    fn push_and_get() {
+        let array = AtomicPartialArray::<u8, 2>::new();
+        assert_eq!(array.capacity(), 2);
+        assert_eq!(array.get(0), None);
+        array.push(1).unwrap();
+        array.push(2).unwrap();
+        assert_eq!(array.push(3).unwrap_err().element(), 3);
+        assert_eq!(array.get(0), Some(&1));
+        assert_eq!(array.get(1), Some(&2));
+        assert_eq!(array.get(2), None);
+        assert_eq!(format!("{:?}", array), "[1, 2]");
+        assert_eq!(array.into_partial_array(), [1, 2]);
+    }
+
+    #[test]
+    fn many_threads() {
+        let array = AtomicPartialArray::<usize, 100>::new();
+        let failed = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for thread in 0..8 {
+                let (array, failed) = (&array, &failed);
+                scope.spawn(move || {
+                    for i in 0..20 {
+                        if array.push(thread * 20 + i).is_err() {
+                            failed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(failed.load(Ordering::Relaxed), 60);
+        let mut values = array.into_partial_array();
+        values.sort_unstable();
+        values
+            .windows(2)
+            .for_each(|pair| assert!(pair[0] < pair[1]));
+        assert_eq!(values.len(), 100);
+    }
+
+    #[test]
+    fn elements_are_dropped_once() {
+        let count = AtomicUsize::new(0);
+        let array = AtomicPartialArray::<_, 4>::new();
+        (0..3).for_each(|_| array.push(DropCounter(&count)).unwrap());
+        drop(array);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+
+        let array = AtomicPartialArray::<_, 4>::new();
+        (0..3).for_each(|_| array.push(DropCounter(&count)).unwrap());
+        let array = array.into_partial_array();
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+        drop(array);
+        assert_eq!(count.load(Ordering::Relaxed), 6);
+    }
+}
+
+#[cfg(loom)]
+mod model {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn concurrent_push() {
+        loom::model(|| {
+            let array = Arc::new(AtomicPartialArray::<usize, 2>::new());
+            let threads: Vec<_> = (0..2)
+                .map(|i| {
+                    let array = array.clone();
+                    thread::spawn(move || array.push(i).unwrap())
+                })
+                .collect();
+            threads.into_iter().for_each(|t| t.join().unwrap());
+
+            let array = Arc::try_unwrap(array).unwrap().into_partial_array();
+            assert!(array == [0, 1] || array == [1, 0]);
+        });
+    }
+
+    #[test]
+    fn concurrent_push_overflow() {
+        loom::model(|| {
+            let array = Arc::new(AtomicPartialArray::<String, 1>::new());
+            let threads: Vec<_> = (0..2)
+                .map(|i| {
+                    let array = array.clone();
+                    thread::spawn(move || array.push(i.to_string()).is_ok())
+                })
+                .collect();
+            let pushed = threads
+                .into_iter()
+                .map(|t| t.join().unwrap())
+                .filter(|&ok| ok)
+                .count();
+
+            assert_eq!(pushed, 1);
+            assert_eq!(
+                Arc::try_unwrap(array).unwrap().into_partial_array().len(),
+                1
+            );
+        });
+    }
+
+    #[test]
+    fn get_while_pushing() {
+        loom::model(|| {
+            let array = Arc::new(AtomicPartialArray::<String, 2>::new());
+            let writer = {
+                let array = array.clone();
+                thread::spawn(move || array.push(String::from("value")).unwrap())
+            };
+
+            if let Some(value) = array.get(0) {
+                assert_eq!(value, "value");
+            }
+            writer.join().unwrap();
+            assert_eq!(array.get(0).map(String::as_str), Some("value"));
+        });
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::binary_heap::{Max, Min, PartialBinaryHeap, PeekMut};
+use crate::PartialArray;
+
+#[derive(Debug)]
+struct Keyed<'a>(u8, #[allow(dead_code)] DropCounter<'a>);
+impl PartialEq for Keyed<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl Eq for Keyed<'_> {}
+impl PartialOrd for Keyed<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Keyed<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+const VALUES: [u8; 10] = [5, 9, 1, 7, 3, 8, 2, 0, 6, 4];
+
+#[test]
+fn max_heap() {
+    let mut heap = PartialBinaryHeap::<_, 10>::new();
+    VALUES.iter().for_each(|&x| heap.push(x));
+    assert_eq!(heap.len(), 10);
+    assert_eq!(heap.peek(), Some(&9));
+    let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(popped, [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn min_heap() {
+    let mut heap = PartialBinaryHeap::<_, 10, Min>::new();
+    VALUES.iter().for_each(|&x| heap.push(x));
+    assert_eq!(heap.peek(), Some(&0));
+    let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(popped, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn full() {
+    let mut heap: PartialBinaryHeap<u8, 2> = [1, 2].iter().copied().collect();
+    assert_eq!(heap.try_push(3).unwrap_err().element(), 3);
+}
+
+#[test]
+#[should_panic(expected = "PartialBinaryHeap is full (capacity 0)")]
+fn push_zero_capacity() {
+    PartialBinaryHeap::<u8, 0>::new().push(1);
+}
+
+#[test]
+fn heapify() {
+    let array = PartialArray::<u8, 16>::from_prefix(VALUES);
+    let heap = PartialBinaryHeap::<_, 16, Max>::from(array.clone());
+    assert_eq!(heap.peek(), Some(&9));
+    assert_eq!(heap.into_sorted_array(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let heap = PartialBinaryHeap::<_, 16, Min>::from(array);
+    assert_eq!(heap.peek(), Some(&0));
+    assert_eq!(heap.into_sorted_array(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn peek_mut() {
+    let mut heap: PartialBinaryHeap<u8, 10> = VALUES.iter().copied().collect();
+    *heap.peek_mut().unwrap() = 0;
+    assert_eq!(heap.peek(), Some(&8));
+    assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()), 8);
+    assert_eq!(heap.pop(), Some(7));
+    assert!(PartialBinaryHeap::<u8, 1>::new().peek_mut().is_none());
+}
+
+#[test]
+fn extend_and_iter() {
+    let mut heap = PartialBinaryHeap::<u8, 4>::new();
+    heap.extend(0..4);
+    assert_eq!(heap.iter().count(), 4);
+    let mut values: Vec<_> = heap.clone().into_iter().collect();
+    values.sort_unstable();
+    assert_eq!(values, [0, 1, 2, 3]);
+    assert_eq!(format!("{:?}", heap).len(), "[0, 1, 2, 3]".len());
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 1 elements to much")]
+fn extend_too_many() {
+    let mut heap = PartialBinaryHeap::<u8, 4>::new();
+    heap.extend(0..5);
+}
+
+#[test]
+fn elements_are_dropped_once() {
+    let count = AtomicUsize::new(0);
+    let mut heap = PartialBinaryHeap::<_, 4>::new();
+    (0..4).for_each(|i| heap.push(Keyed(i, DropCounter(&count))));
+    assert_eq!(heap.pop().map(|x| x.0), Some(3));
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+    drop(heap);
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
@@ -0,0 +1,41 @@
+use crate::PartialArray;
+
+#[test]
+fn roundtrip() {
+    let array: PartialArray<u16, 8> = (300..305).collect();
+    let bytes = borsh::to_vec(&array).unwrap();
+    assert_eq!(
+        borsh::from_slice::<PartialArray<u16, 8>>(&bytes).unwrap(),
+        array
+    );
+}
+
+#[test]
+fn empty() {
+    let array: PartialArray<u16, 8> = PartialArray::default();
+    let bytes = borsh::to_vec(&array).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 0]);
+    assert!(borsh::from_slice::<PartialArray<u16, 8>>(&bytes)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn compatible_with_vec() {
+    let array: PartialArray<u16, 8> = (300..305).collect();
+    let vec: Vec<u16> = (300..305).collect();
+    assert_eq!(borsh::to_vec(&array).unwrap(), borsh::to_vec(&vec).unwrap());
+}
+
+#[test]
+fn reject_too_long() {
+    let bytes = borsh::to_vec(&vec![1_u8; 5]).unwrap();
+    let error = borsh::from_slice::<PartialArray<u8, 4>>(&bytes).unwrap_err();
+    assert_eq!(error.kind(), borsh::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn reject_truncated() {
+    let bytes = borsh::to_vec(&vec![1_u8; 5]).unwrap();
+    assert!(borsh::from_slice::<PartialArray<u8, 8>>(&bytes[..4]).is_err());
+}
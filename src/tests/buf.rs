@@ -0,0 +1,103 @@
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::buf::PartialBuf;
+
+#[test]
+fn extend() {
+    let mut storage = [MaybeUninit::uninit(); 4];
+    let mut buf = PartialBuf::new(&mut storage);
+    assert_eq!(buf.capacity(), 4);
+    buf.extend(1..3);
+    buf.extend(3..5);
+    assert!(buf.is_full());
+    assert_eq!(buf, [1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 2 elements to much")]
+fn extend_too_many() {
+    let mut storage = [MaybeUninit::uninit(); 4];
+    let mut buf = PartialBuf::new(&mut storage);
+    buf.extend(0..6);
+}
+
+#[test]
+fn from_iter_in() {
+    let mut storage = [MaybeUninit::uninit(); 4];
+    let buf = PartialBuf::from_iter_in(&mut storage, (1..4).map(|x| x * 2));
+    assert_eq!(buf, [2, 4, 6]);
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 1 elements to much")]
+fn from_iter_in_too_many() {
+    let dropped = AtomicUsize::new(0);
+    let mut storage = [MaybeUninit::uninit(), MaybeUninit::uninit()];
+    let _ = PartialBuf::from_iter_in(&mut storage, (0..3).map(|_| DropCounter(&dropped)));
+}
+
+#[test]
+#[should_panic(expected = "PartialBuf is full (capacity 0)")]
+fn push_full() {
+    let mut buf = PartialBuf::new(&mut []);
+    buf.push(1);
+}
+
+#[test]
+fn push_pop_truncate() {
+    let mut storage = [MaybeUninit::uninit(); 8];
+    let mut buf = PartialBuf::new(&mut storage);
+    buf.push(1);
+    buf.push(2);
+    buf.push(3);
+    assert_eq!(buf.pop(), Some(3));
+    buf.push(4);
+    buf.truncate(1);
+    assert_eq!(buf, [1]);
+    buf.clear();
+    assert_eq!(buf.pop(), None);
+    assert_eq!(format!("{:?}", buf), "[]");
+}
+
+#[test]
+fn into_initialized() {
+    let mut storage = [MaybeUninit::uninit(); 8];
+    let mut buf = PartialBuf::new(&mut storage);
+    buf.extend(1..4);
+    let values = buf.into_initialized();
+    values[1] = 42;
+    assert_eq!(values, [1, 42, 3]);
+}
+
+#[test]
+fn elements_are_dropped_once() {
+    let count = AtomicUsize::new(0);
+    let mut storage: [MaybeUninit<DropCounter>; 4] = [
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+    ];
+    let mut buf = PartialBuf::new(&mut storage);
+    buf.extend((0..3).map(|_| DropCounter(&count)));
+    buf.truncate(2);
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+    drop(buf);
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn into_initialized_does_not_drop() {
+    let count = AtomicUsize::new(0);
+    let mut storage = [MaybeUninit::uninit(), MaybeUninit::uninit()];
+    let mut buf = PartialBuf::new(&mut storage);
+    buf.push(DropCounter(&count));
+    let values = buf.into_initialized();
+    assert_eq!(values.len(), 1);
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+    // SAFETY: the element is initialized and not used afterwards
+    unsafe { std::ptr::drop_in_place(values) };
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,38 @@
+use crate::{partial_array, PartialArray};
+
+static EMPTY: PartialArray<u8, 4> = PartialArray::new();
+const FULL: PartialArray<u8, 3> = PartialArray::from_array([1, 2, 3]);
+const MACRO: PartialArray<u8, 2> = partial_array![4, 5];
+static TABLE: [PartialArray<u8, 4>; 3] = [
+    PartialArray::from_prefix([]),
+    PartialArray::from_prefix([1, 2]),
+    PartialArray::from_prefix([1, 2, 3, 4]),
+];
+const LEN: usize = TABLE[1].len();
+const CAPACITY: usize = TABLE[1].capacity();
+const IS_EMPTY: bool = TABLE[0].is_empty();
+
+#[test]
+fn statics_and_consts() {
+    assert!(EMPTY.is_empty());
+    assert_eq!(FULL, [1, 2, 3]);
+    assert_eq!(MACRO, [4, 5]);
+    assert_eq!(TABLE[0], []);
+    assert_eq!(TABLE[1], [1, 2]);
+    assert_eq!(TABLE[2], [1, 2, 3, 4]);
+    assert_eq!((LEN, CAPACITY, IS_EMPTY), (2, 4, true));
+}
+
+#[test]
+fn from_prefix_moves_elements() {
+    let array = PartialArray::<String, 4>::from_prefix([String::from("a"), String::from("b")]);
+    assert_eq!(array.len(), 2);
+    assert_eq!(array.capacity(), 4);
+    assert_eq!(array, [String::from("a"), String::from("b")]);
+}
+
+#[test]
+#[should_panic(expected = "array has more elements than the capacity")]
+fn from_prefix_too_many() {
+    let _array = PartialArray::<u8, 2>::from_prefix([1, 2, 3]);
+}
@@ -0,0 +1,70 @@
+use std::fmt::{self, Write};
+use std::thread;
+
+use crate::critical_section::StaticPartialArray;
+
+#[test]
+fn push_and_drain() {
+    static ARRAY: StaticPartialArray<u8, 2> = StaticPartialArray::new();
+    assert!(ARRAY.push(1).is_ok());
+    assert!(ARRAY.push(2).is_ok());
+    assert_eq!(ARRAY.push(3).unwrap_err().element(), 3);
+    assert_eq!(format!("{:?}", ARRAY), "[1, 2]");
+
+    assert_eq!(ARRAY.drain().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(ARRAY.drain().len(), 0);
+    assert!(ARRAY.push(4).is_ok());
+}
+
+#[test]
+fn with() {
+    static ARRAY: StaticPartialArray<u8, 4> = StaticPartialArray::new();
+    ARRAY.with(|array| array.extend(0..3));
+    assert_eq!(ARRAY.with(|array| array.pop()), Some(2));
+    assert_eq!(ARRAY.with(|array| array.len()), 2);
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn nested_access() {
+    static ARRAY: StaticPartialArray<u8, 4> = StaticPartialArray::new();
+    ARRAY.with(|_| ARRAY.push(1).ok());
+}
+
+#[test]
+fn debug_outside_critical_section() {
+    static ARRAY: StaticPartialArray<u8, 4> = StaticPartialArray::new();
+
+    /// An output, which accesses the array while it is written to.
+    struct Output(String);
+    impl Write for Output {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            ARRAY.with(|_| self.0.push_str(s));
+            Ok(())
+        }
+    }
+
+    ARRAY.push(1).unwrap();
+    let mut output = Output(String::new());
+    write!(output, "{:?}", ARRAY).unwrap();
+    assert_eq!(output.0, "[1]");
+}
+
+#[test]
+fn shared_between_threads() {
+    static ARRAY: StaticPartialArray<usize, 400> = StaticPartialArray::new();
+    let producers: Vec<_> = (0..4)
+        .map(|thread| {
+            thread::spawn(move || {
+                (0..100).for_each(|i| ARRAY.push(thread * 100 + i).unwrap());
+            })
+        })
+        .collect();
+    producers
+        .into_iter()
+        .for_each(|producer| producer.join().unwrap());
+
+    let mut values: Vec<_> = ARRAY.drain().collect();
+    values.sort_unstable();
+    assert_eq!(values, (0..400).collect::<Vec<_>>());
+}
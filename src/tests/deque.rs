@@ -0,0 +1,158 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::deque::PartialDeque;
+use crate::PartialArray;
+
+/// Create a deque with the elements `1..=4`, that wraps around the storage.
+fn wrapped() -> PartialDeque<u8, 5> {
+    let mut deque = PartialDeque::new();
+    deque.push_back(3);
+    deque.push_back(4);
+    deque.push_front(2);
+    deque.push_front(1);
+    deque
+}
+
+#[test]
+fn push_and_pop() {
+    let mut deque = wrapped();
+    assert_eq!(deque.len(), 4);
+    assert_eq!(deque, [1, 2, 3, 4]);
+    assert_eq!(deque.front(), Some(&1));
+    assert_eq!(deque.back(), Some(&4));
+    assert_eq!(deque.pop_front(), Some(1));
+    assert_eq!(deque.pop_back(), Some(4));
+    assert_eq!(deque.pop_back(), Some(3));
+    assert_eq!(deque.pop_front(), Some(2));
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    assert_eq!(deque.front(), None);
+    assert_eq!(deque.back(), None);
+}
+
+#[test]
+fn full() {
+    let mut deque = wrapped();
+    deque.push_back(5);
+    assert!(deque.is_full());
+    assert_eq!(deque.try_push_back(6).unwrap_err().element(), 6);
+    assert_eq!(deque.try_push_front(0).unwrap_err().element(), 0);
+}
+
+#[test]
+#[should_panic(expected = "PartialDeque is full (capacity 0)")]
+fn push_zero_capacity() {
+    PartialDeque::<u8, 0>::new().push_front(1);
+}
+
+#[test]
+fn overwrite() {
+    let mut deque = PartialDeque::<u8, 3>::new();
+    for i in 0..3 {
+        assert_eq!(deque.push_back_overwrite(i), None);
+    }
+    assert_eq!(deque.push_back_overwrite(3), Some(0));
+    assert_eq!(deque, [1, 2, 3]);
+    assert_eq!(deque.push_front_overwrite(0), Some(3));
+    assert_eq!(deque, [0, 1, 2]);
+
+    let mut deque = PartialDeque::<u8, 0>::new();
+    assert_eq!(deque.push_back_overwrite(1), Some(1));
+    assert_eq!(deque.push_front_overwrite(2), Some(2));
+}
+
+#[test]
+fn slices() {
+    let mut deque = wrapped();
+    assert_eq!(deque.as_slices(), (&[1, 2][..], &[3, 4][..]));
+    deque.as_mut_slices().1[0] = 42;
+    assert_eq!(deque, [1, 2, 42, 4]);
+
+    assert_eq!(deque.make_contiguous(), [1, 2, 42, 4]);
+    assert_eq!(deque.as_slices(), (&[1, 2, 42, 4][..], &[][..]));
+    deque.push_front(0);
+    assert_eq!(deque, [0, 1, 2, 42, 4]);
+}
+
+#[test]
+fn rotate() {
+    let mut deque = wrapped();
+    deque.rotate_left(1);
+    assert_eq!(deque, [2, 3, 4, 1]);
+    deque.rotate_right(3);
+    assert_eq!(deque, [3, 4, 1, 2]);
+    deque.rotate_left(4);
+    assert_eq!(deque, [3, 4, 1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "cannot rotate by more than the length")]
+fn rotate_too_far() {
+    wrapped().rotate_right(5);
+}
+
+#[test]
+fn index() {
+    let mut deque = wrapped();
+    assert_eq!(deque[2], 3);
+    deque[3] = 0;
+    assert_eq!(deque.get(3), Some(&0));
+    assert_eq!(deque.get(4), None);
+    *deque.back_mut().unwrap() += 1;
+    *deque.front_mut().unwrap() += 1;
+    assert_eq!(deque, [2, 2, 3, 1]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn index_out_of_bounds() {
+    let _value = wrapped()[4];
+}
+
+#[test]
+fn iter() {
+    let mut deque = wrapped();
+    deque.iter_mut().for_each(|x| *x *= 2);
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2, 4, 6, 8]);
+    assert_eq!(deque.into_iter().rev().collect::<Vec<_>>(), [8, 6, 4, 2]);
+}
+
+#[test]
+fn conversions() {
+    let array = PartialArray::<u8, 5>::from_prefix([1, 2, 3]);
+    let mut deque = PartialDeque::from(array);
+    deque.push_front(0);
+    assert_eq!(deque, [0, 1, 2, 3]);
+
+    let array = PartialArray::from(wrapped());
+    assert_eq!(array, [1, 2, 3, 4]);
+}
+
+#[test]
+fn from_iter_clone_debug() {
+    let deque: PartialDeque<u8, 4> = (1..4).collect();
+    assert_eq!(deque.clone(), deque);
+    assert_eq!(format!("{:?}", wrapped()), "[1, 2, 3, 4]");
+    assert_eq!(wrapped(), (1..5).collect::<PartialDeque<u8, 8>>());
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 1 elements to much")]
+fn from_iter_too_many() {
+    let _deque: PartialDeque<u8, 4> = (0..5).collect();
+}
+
+#[test]
+fn elements_are_dropped_once() {
+    let count = AtomicUsize::new(0);
+    let mut deque = PartialDeque::<_, 3>::new();
+    deque.push_back(DropCounter(&count));
+    deque.push_front(DropCounter(&count));
+    deque.push_front(DropCounter(&count));
+    drop(deque.push_back_overwrite(DropCounter(&count)));
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+    deque.rotate_left(1);
+    drop(deque);
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
@@ -0,0 +1,59 @@
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::PartialArray;
+
+#[test]
+fn into_partial_array() {
+    let vec: heapless::Vec<u8, 8> = heapless::Vec::from_slice(&[1, 2, 3]).unwrap();
+    let array = PartialArray::<u8, 3>::try_from(vec).unwrap();
+    assert_eq!(array, [1, 2, 3]);
+}
+
+#[test]
+fn into_partial_array_too_small() {
+    let vec: heapless::Vec<u8, 8> = heapless::Vec::from_slice(&[1, 2, 3]).unwrap();
+    let vec = PartialArray::<u8, 2>::try_from(vec).unwrap_err().element();
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+#[test]
+fn from_partial_array() {
+    let array: PartialArray<u8, 8> = (1..4).collect();
+    let vec = heapless::Vec::<u8, 3>::try_from(array).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+#[test]
+fn from_partial_array_too_small() {
+    let array: PartialArray<u8, 8> = (1..4).collect();
+    let array = heapless::Vec::<u8, 2>::try_from(array)
+        .unwrap_err()
+        .element();
+    assert_eq!(array, [1, 2, 3]);
+}
+
+#[test]
+fn elements_are_moved() {
+    let count = AtomicUsize::new(0);
+
+    let array: PartialArray<_, 4> = vec![DropCounter(&count), DropCounter(&count)]
+        .into_iter()
+        .collect();
+    let vec = heapless::Vec::<_, 2>::try_from(array).unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+    let array = PartialArray::<_, 2>::try_from(vec).unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+    drop(array);
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn eq() {
+    let array: PartialArray<u8, 8> = (1..4).collect();
+    let vec: heapless::Vec<u8, 4> = heapless::Vec::from_slice(&[1, 2, 3]).unwrap();
+    assert_eq!(array, vec);
+    assert_eq!(vec, array);
+    assert_ne!(PartialArray::<u8, 8>::default(), vec);
+}
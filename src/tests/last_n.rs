@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::last_n::LastN;
+use crate::PartialArray;
+
+#[test]
+fn fewer_than_n() {
+    let last: LastN<u8, 4> = (1..3).collect();
+    assert_eq!(last.len(), 2);
+    assert_eq!(last.into_partial_array(), [1, 2]);
+}
+
+#[test]
+fn more_than_n() {
+    let last: LastN<u32, 4> = (0..1000).collect();
+    assert_eq!(last.len(), 4);
+    assert_eq!(format!("{:?}", last), "[996, 997, 998, 999]");
+    assert_eq!(PartialArray::from(last), [996, 997, 998, 999]);
+}
+
+#[test]
+fn extend_keeps_order() {
+    let mut last: LastN<u8, 3> = (0..2).collect();
+    last.extend(2..4);
+    assert_eq!(last.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    last.extend(4..5);
+    assert_eq!(last.into_iter().collect::<Vec<_>>(), [2, 3, 4]);
+}
+
+#[test]
+fn zero_capacity() {
+    let last: LastN<u8, 0> = (0..10).collect();
+    assert!(last.is_empty());
+}
+
+#[test]
+fn evicted_elements_are_dropped() {
+    let count = AtomicUsize::new(0);
+    let last: LastN<_, 2> = (0..5).map(|_| DropCounter(&count)).collect();
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+    drop(last);
+    assert_eq!(count.load(Ordering::Relaxed), 5);
+}
@@ -0,0 +1,124 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::lru::PartialLru;
+
+fn keys<const N: usize>(lru: &PartialLru<u8, u8, N>) -> Vec<u8> {
+    lru.iter().map(|(&key, _)| key).collect()
+}
+
+#[test]
+fn recency_order() {
+    let mut lru = PartialLru::<u8, u8, 4>::new();
+    for key in 0..4 {
+        assert_eq!(lru.put(key, key * 10), None);
+    }
+    assert_eq!(keys(&lru), [3, 2, 1, 0]);
+
+    assert_eq!(lru.get(&1), Some(&10));
+    assert_eq!(keys(&lru), [1, 3, 2, 0]);
+    *lru.get_mut(&0).unwrap() += 1;
+    assert_eq!(keys(&lru), [0, 1, 3, 2]);
+
+    // peeking does not promote
+    assert_eq!(lru.peek(&2), Some(&20));
+    assert!(lru.contains_key(&3));
+    assert_eq!(keys(&lru), [0, 1, 3, 2]);
+    assert_eq!(format!("{:?}", lru), "{0: 1, 1: 10, 3: 30, 2: 20}");
+    assert_eq!(
+        lru.iter().rev().map(|(&key, _)| key).collect::<Vec<_>>(),
+        [2, 3, 1, 0]
+    );
+}
+
+#[test]
+fn put_evicts_lru() {
+    let mut lru = PartialLru::<u8, u8, 3>::new();
+    for key in 0..3 {
+        lru.put(key, key);
+    }
+    lru.get(&0);
+    assert_eq!(lru.put(3, 3), Some((1, 1)));
+    assert_eq!(lru.put(4, 4), Some((2, 2)));
+    assert_eq!(keys(&lru), [4, 3, 0]);
+    assert_eq!(lru.get(&1), None);
+}
+
+#[test]
+fn put_replaces() {
+    let mut lru = PartialLru::<u8, u8, 2>::new();
+    lru.put(1, 1);
+    lru.put(2, 2);
+    assert_eq!(lru.put(1, 10), Some((1, 1)));
+    assert_eq!(lru.len(), 2);
+    assert_eq!(keys(&lru), [1, 2]);
+    assert_eq!(lru.peek(&1), Some(&10));
+}
+
+#[test]
+fn remove_and_pop() {
+    let mut lru = PartialLru::<u8, u8, 4>::new();
+    for key in 0..4 {
+        lru.put(key, key);
+    }
+    // removing from the middle moves the last stored entry into the gap
+    assert_eq!(lru.remove(&1), Some(1));
+    assert_eq!(lru.remove(&1), None);
+    assert_eq!(keys(&lru), [3, 2, 0]);
+
+    assert_eq!(lru.pop_lru(), Some((0, 0)));
+    assert_eq!(lru.remove(&3), Some(3));
+    assert_eq!(keys(&lru), [2]);
+    lru.put(5, 5);
+    assert_eq!(keys(&lru), [5, 2]);
+
+    assert_eq!(lru.pop_lru(), Some((2, 2)));
+    assert_eq!(lru.pop_lru(), Some((5, 5)));
+    assert_eq!(lru.pop_lru(), None);
+    assert!(lru.is_empty());
+}
+
+#[test]
+fn borrowed_keys() {
+    let mut lru = PartialLru::<String, u8, 2>::new();
+    lru.put("a".to_string(), 1);
+    assert_eq!(lru.get("a"), Some(&1));
+    assert_eq!(lru.remove("a"), Some(1));
+}
+
+#[test]
+fn zero_capacity() {
+    let mut lru = PartialLru::<u8, u8, 0>::new();
+    assert_eq!(lru.put(1, 1), Some((1, 1)));
+    assert!(lru.is_empty());
+}
+
+#[test]
+fn clear_and_clone() {
+    let mut lru = PartialLru::<u8, u8, 3>::new();
+    for key in 0..3 {
+        lru.put(key, key);
+    }
+    let clone = lru.clone();
+    lru.clear();
+    assert!(lru.is_empty());
+    assert_eq!(lru.iter().next(), None);
+    assert_eq!(keys(&clone), [2, 1, 0]);
+
+    lru.put(7, 7);
+    assert_eq!(keys(&lru), [7]);
+}
+
+#[test]
+fn drop_entries() {
+    let dropped = AtomicUsize::new(0);
+    let mut lru = PartialLru::<u8, _, 2>::new();
+    lru.put(0, DropCounter(&dropped));
+    lru.put(1, DropCounter(&dropped));
+
+    drop(lru.put(2, DropCounter(&dropped)));
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    drop(lru);
+    assert_eq!(dropped.load(Ordering::Relaxed), 3);
+}
@@ -0,0 +1,151 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::map::{Entry, PartialMap};
+use crate::PartialArray;
+
+#[test]
+fn insert_get_remove() {
+    let mut map = PartialMap::<u8, &str, 4>::new();
+    assert_eq!(map.insert(3, "c"), None);
+    assert_eq!(map.insert(1, "a"), None);
+    assert_eq!(map.insert(2, "b"), None);
+    assert_eq!(map.insert(1, "A"), Some("a"));
+    assert_eq!(map.len(), 3);
+
+    assert_eq!(map.get(&1), Some(&"A"));
+    assert_eq!(map.get(&4), None);
+    assert_eq!(map[&3], "c");
+    assert!(map.contains_key(&2));
+    *map.get_mut(&2).unwrap() = "B";
+    assert_eq!(map.get_key_value(&2), Some((&2, &"B")));
+
+    assert_eq!(map.remove(&2), Some("B"));
+    assert_eq!(map.remove(&2), None);
+    assert_eq!(map.remove_entry(&3), Some((3, "c")));
+    assert_eq!(format!("{:?}", map), r#"{1: "A"}"#);
+}
+
+#[test]
+fn borrowed_keys() {
+    let map: PartialMap<String, u8, 2> = vec![("b".to_string(), 2), ("a".to_string(), 1)]
+        .into_iter()
+        .collect();
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map["b"], 2);
+}
+
+#[test]
+#[should_panic(expected = "key not found")]
+fn index_missing() {
+    let map = PartialMap::<u8, u8, 2>::new();
+    let _ = map[&0];
+}
+
+#[test]
+fn deterministic_order() {
+    let a: PartialMap<u8, char, 8> = [(5, 'e'), (1, 'a'), (3, 'c')].iter().copied().collect();
+    let b: PartialMap<u8, char, 8> = [(3, 'c'), (5, 'e'), (1, 'a')].iter().copied().collect();
+    assert_eq!(a, b);
+    assert_eq!(a.keys().copied().collect::<Vec<_>>(), [1, 3, 5]);
+    assert_eq!(
+        a.values().rev().copied().collect::<Vec<_>>(),
+        ['e', 'c', 'a']
+    );
+    assert_eq!(a.iter().len(), 3);
+    assert_eq!(
+        a.into_iter().collect::<Vec<_>>(),
+        [(1, 'a'), (3, 'c'), (5, 'e')]
+    );
+}
+
+#[test]
+fn iter_mut() {
+    let mut map: PartialMap<u8, u32, 4> = (0..4).map(|key| (key, 0)).collect();
+    for (key, value) in &mut map {
+        *value = u32::from(*key) * 10;
+    }
+    assert_eq!(PartialArray::from(map), [(0, 0), (1, 10), (2, 20), (3, 30)]);
+}
+
+#[test]
+#[should_panic(expected = "PartialMap is full (capacity 2)")]
+fn insert_full() {
+    let mut map: PartialMap<u8, u8, 2> = [(0, 0), (1, 1)].iter().copied().collect();
+    // replacing does not need space
+    map.insert(1, 2);
+    map.insert(2, 2);
+}
+
+#[test]
+fn try_from_iter() {
+    let map = PartialMap::<u8, u8, 2>::try_from_iter([(1, 1), (2, 2), (1, 3)]).unwrap();
+    assert_eq!(map.get(&1), Some(&3));
+
+    let error = PartialMap::<u8, u8, 2>::try_from_iter((0..5).map(|x| (x, x))).unwrap_err();
+    assert_eq!(error.element(), (2, 2));
+}
+
+#[test]
+#[should_panic(expected = "PartialMap is full (capacity 2)")]
+fn from_iter_overflow() {
+    let _: PartialMap<u8, u8, 2> = (0..3).map(|x| (x, x)).collect();
+}
+
+#[test]
+fn entry() {
+    let mut map = PartialMap::<&str, u32, 4>::new();
+    *map.entry("a").or_insert(1) += 10;
+    *map.entry("a").or_insert(1) += 10;
+    map.entry("b").and_modify(|value| *value += 1).or_default();
+    map.entry("b").and_modify(|value| *value += 1).or_default();
+    assert_eq!(map.get("a"), Some(&21));
+    assert_eq!(map.get("b"), Some(&1));
+
+    match map.entry("c") {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), &"c");
+            assert_eq!(format!("{:?}", entry), r#"VacantEntry("c")"#);
+            *entry.insert(3) += 1;
+        }
+        Entry::Occupied(_) => unreachable!(),
+    }
+    match map.entry("a") {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.insert(0), 21);
+            assert_eq!(entry.get(), &0);
+            assert_eq!(entry.remove_entry(), ("a", 0));
+        }
+        Entry::Vacant(_) => unreachable!(),
+    }
+    assert_eq!(format!("{:?}", map), r#"{"b": 1, "c": 4}"#);
+}
+
+#[test]
+fn entry_try_insert_full() {
+    let mut map: PartialMap<u8, u8, 1> = [(0, 0)].iter().copied().collect();
+    match map.entry(1) {
+        Entry::Vacant(entry) => assert_eq!(entry.try_insert(1).unwrap_err().element(), (1, 1)),
+        Entry::Occupied(_) => unreachable!(),
+    }
+    assert_eq!(map.try_insert(0, 5).ok(), Some(Some(0)));
+}
+
+#[test]
+fn drop_values() {
+    let dropped = AtomicUsize::new(0);
+    let mut map = PartialMap::<_, _, 4>::new();
+    for key in 0..4 {
+        map.insert(key, DropCounter(&dropped));
+    }
+
+    // the replaced value
+    drop(map.insert(2, DropCounter(&dropped)));
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    map.remove(&0);
+    assert_eq!(dropped.load(Ordering::Relaxed), 2);
+
+    drop(map);
+    assert_eq!(dropped.load(Ordering::Relaxed), 5);
+}
@@ -1,8 +1,65 @@
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(target_has_atomic = "ptr")]
+mod atomic;
+mod binary_heap;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod buf;
+mod const_fn;
+#[cfg(feature = "critical-section")]
+mod critical_section;
 mod debug;
+mod deque;
 mod deref;
 mod drop;
 mod eq;
 mod extend;
 mod from_iter;
+#[cfg(feature = "heapless")]
+mod heapless;
 mod into_iter;
+mod last_n;
+mod lru;
+mod map;
+#[cfg(feature = "bytemuck")]
+mod pod;
+mod pop;
+#[cfg(feature = "proptest")]
+mod proptest;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "parity-scale-codec")]
+mod scale;
+mod set;
 mod size_layout;
+mod slab;
+mod slice;
+mod smallest_k;
+mod sorted;
+mod sparse;
+#[cfg(feature = "alloc")]
+mod spill;
+#[cfg(target_has_atomic = "ptr")]
+mod spsc;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "zeroize")]
+mod zeroize;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An element, which counts how often it is dropped.
+#[derive(Debug, Clone)]
+struct DropCounter<'a>(&'a AtomicUsize);
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
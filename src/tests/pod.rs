@@ -0,0 +1,83 @@
+use crate::pod::{PodPartialArray, TryFromBytesError};
+use crate::PartialArray;
+use bytemuck::{PodCastError, Zeroable};
+use core::mem;
+
+#[test]
+fn size_of() {
+    assert_eq!(mem::size_of::<PodPartialArray<u32, 8>>(), 4 + 8 * 4);
+    assert_eq!(mem::size_of::<PodPartialArray<[f32; 3], 2>>(), 4 + 2 * 12);
+}
+
+#[test]
+fn zeroed_is_empty() {
+    assert!(PodPartialArray::<u32, 8>::zeroed().is_empty());
+    assert!(PartialArray::<String, 8>::zeroed().is_empty());
+}
+
+#[test]
+fn bytes_roundtrip() {
+    let array: PodPartialArray<u32, 4> = [7, 8, 9]
+        .iter()
+        .copied()
+        .collect::<PartialArray<_, 4>>()
+        .into();
+    let bytes = bytemuck::bytes_of(&array);
+    assert_eq!(&bytes[..4], &3_u32.to_ne_bytes());
+    assert_eq!(&bytes[4..8], &7_u32.to_ne_bytes());
+
+    let view = PodPartialArray::<u32, 4>::try_from_bytes(bytes).unwrap();
+    assert_eq!(*view, [7, 8, 9]);
+}
+
+#[test]
+fn non_filled_are_zeroed() {
+    let array: PodPartialArray<u32, 4> = [7].iter().copied().collect::<PartialArray<_, 4>>().into();
+    assert_eq!(&bytemuck::bytes_of(&array)[8..], &[0; 12]);
+}
+
+#[test]
+fn reject_too_long() {
+    let words: [u32; 3] = [3, 1, 2];
+    let result = PodPartialArray::<u32, 2>::try_from_bytes(bytemuck::bytes_of(&words));
+    assert_eq!(result.unwrap_err(), TryFromBytesError::Length(3));
+}
+
+#[test]
+fn reject_wrong_size() {
+    let words: [u32; 4] = [1, 1, 2, 3];
+    let result = PodPartialArray::<u32, 2>::try_from_bytes(bytemuck::bytes_of(&words));
+    assert_eq!(
+        result.unwrap_err(),
+        TryFromBytesError::Cast(PodCastError::SizeMismatch)
+    );
+}
+
+#[test]
+fn modify_through_bytes() {
+    let mut words: [u32; 3] = [1, 1, 2];
+    let array = PodPartialArray::<u32, 2>::try_from_bytes_mut(bytemuck::bytes_of_mut(&mut words));
+    array.unwrap()[0] = 42;
+    assert_eq!(words, [1, 42, 2]);
+}
+
+#[test]
+fn into_partial_array() {
+    let array: PodPartialArray<u32, 4> = [7, 8]
+        .iter()
+        .copied()
+        .collect::<PartialArray<_, 4>>()
+        .into();
+    let array: PartialArray<u32, 4> = array.into();
+    assert_eq!(array, [7, 8]);
+}
+
+#[test]
+#[should_panic]
+fn deref_invalid_len() {
+    let mut words: [u32; 3] = [0; 3];
+    let array =
+        bytemuck::from_bytes_mut::<PodPartialArray<u32, 2>>(bytemuck::bytes_of_mut(&mut words));
+    bytemuck::bytes_of_mut(array)[0] = 3;
+    let _len = array.len();
+}
@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::PartialArray;
+
+#[test]
+fn pop() {
+    let mut partial_array: PartialArray<u8, 4> = [1, 2].iter().copied().collect();
+    assert_eq!(partial_array.pop(), Some(2));
+    assert_eq!(partial_array.pop(), Some(1));
+    assert_eq!(partial_array.pop(), None);
+    assert!(partial_array.is_empty());
+}
+
+#[test]
+fn pop_does_not_drop() {
+    let count = AtomicUsize::new(0);
+
+    let mut partial_array: PartialArray<_, 4> = vec![DropCounter(&count), DropCounter(&count)]
+        .into_iter()
+        .collect();
+    let popped = partial_array.pop();
+    drop(partial_array);
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+
+    drop(popped);
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn truncate() {
+    let count = AtomicUsize::new(0);
+
+    let mut partial_array: PartialArray<_, 4> = vec![
+        DropCounter(&count),
+        DropCounter(&count),
+        DropCounter(&count),
+    ]
+    .into_iter()
+    .collect();
+    partial_array.truncate(1);
+    assert_eq!(partial_array.len(), 1);
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+
+    drop(partial_array);
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn truncate_longer() {
+    let mut partial_array: PartialArray<u8, 4> = [1, 2].iter().copied().collect();
+    partial_array.truncate(3);
+    assert_eq!(partial_array, [1, 2]);
+}
+
+#[test]
+fn clear() {
+    let mut partial_array: PartialArray<u8, 4> = [1, 2].iter().copied().collect();
+    partial_array.clear();
+    assert!(partial_array.is_empty());
+
+    partial_array.extend(Some(3));
+    assert_eq!(partial_array, [3]);
+}
@@ -0,0 +1,44 @@
+use crate::proptest::partial_array;
+use crate::PartialArray;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::TestRunner;
+
+#[test]
+fn biased_towards_bounds() {
+    let mut runner = TestRunner::deterministic();
+    let strategy = partial_array::<_, 8>(any::<u8>(), 2..=6);
+    let lengths: Vec<_> = (0..200)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current().len())
+        .collect();
+    assert!(lengths.iter().all(|len| (2..=6).contains(len)));
+    assert!(lengths.contains(&2));
+    assert!(lengths.contains(&6));
+}
+
+#[test]
+#[should_panic(expected = "length range exceeds the capacity of 4")]
+fn range_exceeds_capacity() {
+    let _strategy = partial_array::<_, 4>(any::<u8>(), 0..=5);
+}
+
+#[test]
+fn shrinks_towards_empty() {
+    let mut runner = TestRunner::deterministic();
+    let mut tree = any::<PartialArray<u8, 8>>().new_tree(&mut runner).unwrap();
+    while tree.simplify() {}
+    assert!(tree.current().is_empty());
+}
+
+proptest! {
+    #[test]
+    fn any_is_bounded(array in any::<PartialArray<u16, 5>>()) {
+        prop_assert!(array.len() <= 5);
+    }
+
+    #[test]
+    fn elements_from_strategy(array in partial_array::<_, 5>(10..20_u8, 1..=5)) {
+        prop_assert!(!array.is_empty());
+        prop_assert!(array.iter().all(|x| (10..20).contains(x)));
+    }
+}
@@ -0,0 +1,39 @@
+use crate::PartialArray;
+use quickcheck::{Arbitrary, Gen};
+
+#[test]
+fn bounded_length() {
+    let mut g = Gen::new(100);
+    for _ in 0..100 {
+        let array = PartialArray::<u8, 7>::arbitrary(&mut g);
+        assert!(array.len() <= 7);
+    }
+}
+
+#[test]
+fn biased_towards_empty_and_full() {
+    let mut g = Gen::new(100);
+    let lengths: Vec<_> = (0..200)
+        .map(|_| PartialArray::<u8, 7>::arbitrary(&mut g).len())
+        .collect();
+    assert!(lengths.contains(&0));
+    assert!(lengths.contains(&7));
+}
+
+#[test]
+fn shrink() {
+    let array: PartialArray<u8, 4> = [10, 20, 30].iter().copied().collect();
+    let shrunk: Vec<_> = array.shrink().collect();
+    assert!(!shrunk.is_empty());
+    assert!(shrunk.iter().all(|shrunk| shrunk.len() <= 3));
+    assert!(shrunk.iter().any(|shrunk| shrunk.is_empty()));
+}
+
+quickcheck::quickcheck! {
+    fn reverse_twice(array: PartialArray<u32, 16>) -> bool {
+        let mut reversed = array.clone();
+        reversed.reverse();
+        reversed.reverse();
+        reversed == array
+    }
+}
@@ -0,0 +1,46 @@
+use crate::PartialArray;
+use rayon::prelude::*;
+
+#[test]
+fn collect_indexed() {
+    let array: PartialArray<u32, 1000> = (0..1000_u32).into_par_iter().map(|x| x * 2).collect();
+    assert_eq!(array.len(), 1000);
+    assert!(array.iter().copied().eq((0..1000).map(|x| x * 2)));
+}
+
+#[test]
+fn collect_unindexed() {
+    let array: PartialArray<u32, 1000> = (0..1000_u32)
+        .into_par_iter()
+        .filter(|x| x % 3 == 0)
+        .collect();
+    assert_eq!(array.len(), 334);
+    assert!(array.iter().copied().eq((0..1000).filter(|x| x % 3 == 0)));
+}
+
+#[test]
+fn par_extend() {
+    let mut array: PartialArray<u32, 8> = (0..3).collect();
+    array.par_extend((3..8_u32).into_par_iter());
+    assert_eq!(array, [0, 1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 2 elements to much")]
+fn overflow_indexed() {
+    let _array: PartialArray<u32, 8> = (0..10_u32).into_par_iter().collect();
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 1 elements to much")]
+fn overflow_unindexed() {
+    let _array: PartialArray<u32, 4> = (0..10_u32).into_par_iter().filter(|x| x % 2 == 0).collect();
+}
+
+#[test]
+fn par_iter() {
+    let mut array: PartialArray<u32, 8> = (0..5).collect();
+    (&mut array).into_par_iter().for_each(|x| *x += 1);
+    assert_eq!((&array).into_par_iter().sum::<u32>(), 15);
+    assert_eq!(array.par_iter().max(), Some(&5));
+}
@@ -0,0 +1,86 @@
+use crate::rkyv::ArchivedPartialArray;
+use crate::PartialArray;
+use core::mem::{self, MaybeUninit};
+use rkyv::api::low::{access, from_bytes, to_bytes_in_with_alloc};
+use rkyv::rancor::Failure;
+use rkyv::ser::{allocator::SubAllocator, writer::Buffer};
+use rkyv::util::Align;
+use rkyv::Archived;
+
+type Archive = Align<[MaybeUninit<u8>; 256]>;
+
+fn to_bytes<const N: usize>(array: &PartialArray<u32, N>, output: &mut Archive) -> usize {
+    let mut alloc = [MaybeUninit::<u8>::uninit(); 64];
+    let buffer = to_bytes_in_with_alloc::<_, _, Failure>(
+        array,
+        Buffer::from(&mut **output),
+        SubAllocator::new(&mut alloc),
+    )
+    .unwrap();
+    buffer.len()
+}
+
+fn bytes(output: &Archive, len: usize) -> &[u8] {
+    // SAFETY: the serializer initialized the first `len` bytes of the output
+    unsafe { mem::transmute::<&[MaybeUninit<u8>], &[u8]>(&output[..len]) }
+}
+
+#[test]
+fn access_partial() {
+    let array: PartialArray<u32, 8> = (10..13).collect();
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let len = to_bytes(&array, &mut output);
+
+    let archived = access::<ArchivedPartialArray<Archived<u32>, 8>, Failure>(bytes(&output, len));
+    let archived = archived.unwrap();
+    assert_eq!(archived.len(), 3);
+    assert_eq!(archived[0], 10);
+    assert_eq!(archived[2], 12);
+    assert_eq!(*archived, array);
+}
+
+#[test]
+fn access_empty() {
+    let array: PartialArray<u32, 8> = PartialArray::default();
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let len = to_bytes(&array, &mut output);
+
+    let archived = access::<ArchivedPartialArray<Archived<u32>, 8>, Failure>(bytes(&output, len));
+    assert!(archived.unwrap().is_empty());
+}
+
+#[test]
+fn size_is_independent_of_length() {
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let empty = to_bytes(&PartialArray::<u32, 8>::default(), &mut output);
+    let full = to_bytes(&PartialArray::from([1_u32; 8]), &mut output);
+    assert_eq!(empty, full);
+    assert_eq!(
+        mem::size_of::<ArchivedPartialArray<Archived<u32>, 8>>(),
+        4 + 8 * 4
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let array: PartialArray<u32, 8> = (0..5).map(|x| x * 3).collect();
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let len = to_bytes(&array, &mut output);
+
+    let deserialized = from_bytes::<PartialArray<u32, 8>, Failure>(bytes(&output, len));
+    assert_eq!(deserialized.unwrap(), array);
+}
+
+#[test]
+fn reject_too_long() {
+    let array = PartialArray::from([1_u32; 4]);
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let len = to_bytes(&array, &mut output);
+
+    // patch the length of the root object to be greater than the capacity
+    let root = len - mem::size_of::<ArchivedPartialArray<Archived<u32>, 4>>();
+    output[root] = MaybeUninit::new(5);
+
+    let archived = access::<ArchivedPartialArray<Archived<u32>, 4>, Failure>(bytes(&output, len));
+    assert!(archived.is_err());
+}
@@ -0,0 +1,47 @@
+use crate::PartialArray;
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+
+#[test]
+fn roundtrip() {
+    let array: PartialArray<u16, 8> = (300..305).collect();
+    let bytes = array.encode();
+    assert_eq!(
+        PartialArray::<u16, 8>::decode(&mut &bytes[..]).unwrap(),
+        array
+    );
+}
+
+#[test]
+fn empty() {
+    let array: PartialArray<u16, 8> = PartialArray::default();
+    let bytes = array.encode();
+    assert_eq!(bytes, [0]);
+    assert!(PartialArray::<u16, 8>::decode(&mut &bytes[..])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn compatible_with_vec() {
+    let array: PartialArray<u16, 8> = (300..305).collect();
+    let vec: Vec<u16> = (300..305).collect();
+    assert_eq!(array.encode(), vec.encode());
+}
+
+#[test]
+fn reject_too_long() {
+    let bytes = vec![1_u8; 5].encode();
+    assert!(PartialArray::<u8, 4>::decode(&mut &bytes[..]).is_err());
+}
+
+#[test]
+fn max_encoded_len() {
+    assert_eq!(PartialArray::<u16, 8>::max_encoded_len(), 1 + 8 * 2);
+    assert_eq!(PartialArray::<u8, 64>::max_encoded_len(), 2 + 64);
+
+    let full = PartialArray::<u16, 8>::from([u16::MAX; 8]);
+    assert_eq!(
+        full.encode().len(),
+        PartialArray::<u16, 8>::max_encoded_len()
+    );
+}
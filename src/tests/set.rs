@@ -0,0 +1,121 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::set::PartialSet;
+use crate::PartialArray;
+
+fn set<const N: usize>(values: &[u8]) -> PartialSet<u8, N> {
+    values.iter().copied().collect()
+}
+
+#[test]
+fn insert_contains_remove() {
+    let mut set = PartialSet::<u8, 4>::new();
+    assert!(set.insert(3));
+    assert!(set.insert(1));
+    assert!(!set.insert(3));
+    assert_eq!(set.len(), 2);
+
+    assert!(set.contains(&1));
+    assert!(!set.contains(&2));
+    assert_eq!(set.get(&3), Some(&3));
+
+    assert!(set.remove(&1));
+    assert!(!set.remove(&1));
+    assert_eq!(set.take(&3), Some(3));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn borrowed_lookup() {
+    let set: PartialSet<String, 2> = vec!["b".to_string(), "a".to_string()].into_iter().collect();
+    assert!(set.contains("a"));
+    assert_eq!(set.get("b").map(String::as_str), Some("b"));
+}
+
+#[test]
+fn from_iter_deduplicates() {
+    let set: PartialSet<u8, 4> = set(&[12, 13, 8, 12, 255, 8, 8, 8]);
+    assert_eq!(set, [8, 12, 13, 255]);
+    assert_eq!(format!("{:?}", set), "{8, 12, 13, 255}");
+    assert_eq!(PartialArray::from(set), [8, 12, 13, 255]);
+}
+
+#[test]
+#[should_panic(expected = "PartialSet is full (capacity 2)")]
+fn from_iter_overflow() {
+    let _ = set::<2>(&[1, 1, 2, 3]);
+}
+
+#[test]
+fn try_insert_full() {
+    let mut set = set::<2>(&[1, 2]);
+    assert_eq!(set.try_insert(2).ok(), Some(false));
+    assert_eq!(set.try_insert(0).unwrap_err().element(), 0);
+    assert_eq!(
+        PartialSet::<u8, 1>::try_from_iter(0..2)
+            .unwrap_err()
+            .element(),
+        1
+    );
+}
+
+#[test]
+fn set_algebra() {
+    let a = set::<4>(&[1, 2, 3]);
+    let b = set::<8>(&[2, 3, 4, 5]);
+
+    assert_eq!(a.union(&b).unwrap_err(), crate::CapacityError::new(()));
+    assert_eq!(set::<8>(&[1, 2, 3]).union(&b).unwrap(), [1, 2, 3, 4, 5]);
+    assert_eq!(a.intersection(&b), [2, 3]);
+    assert_eq!(a.difference(&b), [1]);
+    assert_eq!(a.symmetric_difference(&b).unwrap(), [1, 4, 5]);
+    assert_eq!(a.union(&PartialSet::<u8, 0>::new()).unwrap(), a);
+}
+
+#[test]
+fn relations() {
+    let a = set::<4>(&[2, 3]);
+    let b = set::<8>(&[1, 2, 3]);
+    let c = set::<4>(&[4]);
+
+    assert!(a.is_subset(&b));
+    assert!(!b.is_subset(&a));
+    assert!(b.is_superset(&a));
+    assert!(a.is_disjoint(&c));
+    assert!(!a.is_disjoint(&b));
+    assert!(PartialSet::<u8, 0>::new().is_subset(&a));
+}
+
+#[test]
+fn drop_elements() {
+    /// A [`DropCounter`] ordered by a key.
+    struct Keyed<'a>(u8, #[allow(dead_code)] DropCounter<'a>);
+    impl PartialEq for Keyed<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Keyed<'_> {}
+    impl PartialOrd for Keyed<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Keyed<'_> {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let dropped = AtomicUsize::new(0);
+    let mut set = PartialSet::<_, 4>::new();
+    for key in [1, 2, 1, 3] {
+        set.insert(Keyed(key, DropCounter(&dropped)));
+    }
+    // the duplicate is dropped right away
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    drop(set);
+    assert_eq!(dropped.load(Ordering::Relaxed), 4);
+}
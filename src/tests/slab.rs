@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::slab::PartialSlab;
+
+#[test]
+fn insert_get_remove() {
+    let mut slab = PartialSlab::<char, 4>::new();
+    let a = slab.insert('a');
+    let b = slab.insert('b');
+    assert_eq!(slab.len(), 2);
+    assert_eq!(slab.get(a), Some(&'a'));
+    assert_eq!(slab[b], 'b');
+
+    slab[b] = 'B';
+    *slab.get_mut(a).unwrap() = 'A';
+    assert_eq!(slab.remove(b), Some('B'));
+    assert_eq!(slab.remove(b), None);
+    assert!(!slab.contains(b));
+    assert_eq!(slab.get_mut(b), None);
+    assert_eq!(slab.len(), 1);
+    assert_eq!(slab[a], 'A');
+}
+
+#[test]
+fn stale_keys() {
+    let mut slab = PartialSlab::<u8, 1>::new();
+    let old = slab.insert(1);
+    slab.remove(old);
+    let new = slab.insert(2);
+
+    assert_eq!(old.index(), new.index());
+    assert_ne!(old.generation(), new.generation());
+    assert_eq!(slab.get(old), None);
+    assert_eq!(slab.remove(old), None);
+    assert_eq!(slab.get(new), Some(&2));
+}
+
+#[test]
+#[should_panic(expected = "invalid key")]
+fn index_stale() {
+    let mut slab = PartialSlab::<u8, 1>::new();
+    let key = slab.insert(1);
+    slab.clear();
+    let _ = slab[key];
+}
+
+#[test]
+fn reuse_slots() {
+    let mut slab = PartialSlab::<u8, 3>::new();
+    let keys: Vec<_> = (0..3).map(|value| slab.insert(value)).collect();
+    assert!(slab.is_full());
+    assert_eq!(slab.try_insert(3).unwrap_err().element(), 3);
+
+    // the most recently freed slot is reused first
+    slab.remove(keys[0]);
+    slab.remove(keys[2]);
+    assert_eq!(slab.insert(4).index(), 2);
+    assert_eq!(slab.insert(5).index(), 0);
+    assert!(slab.is_full());
+}
+
+#[test]
+#[should_panic(expected = "PartialSlab is full (capacity 2)")]
+fn insert_full() {
+    let mut slab = PartialSlab::<u8, 2>::new();
+    for value in 0..3 {
+        slab.insert(value);
+    }
+}
+
+#[test]
+fn iterate_occupied() {
+    let mut slab = PartialSlab::<u32, 8>::new();
+    let keys: Vec<_> = (0..5).map(|value| slab.insert(value)).collect();
+    slab.remove(keys[1]);
+    slab.remove(keys[3]);
+
+    let iter = slab.iter();
+    assert_eq!(iter.len(), 3);
+    let entries: Vec<_> = iter.map(|(key, &value)| (key, value)).collect();
+    assert_eq!(entries, [(keys[0], 0), (keys[2], 2), (keys[4], 4)]);
+
+    for (_, value) in &mut slab {
+        *value *= 10;
+    }
+    let values: Vec<_> = (&slab).into_iter().map(|(_, &value)| value).collect();
+    assert_eq!(values, [0, 20, 40]);
+    assert_eq!(
+        format!("{:?}", slab),
+        format!("{{{:?}: 0, {:?}: 20, {:?}: 40}}", keys[0], keys[2], keys[4])
+    );
+}
+
+#[test]
+fn retain() {
+    let mut slab = PartialSlab::<u32, 8>::new();
+    let keys: Vec<_> = (0..6).map(|value| slab.insert(value)).collect();
+    slab.retain(|key, value| {
+        *value += 1;
+        key != keys[0] && *value % 2 == 0
+    });
+    let values: Vec<_> = slab.iter().map(|(_, &value)| value).collect();
+    assert_eq!(values, [2, 4, 6]);
+    assert!(!slab.contains(keys[0]));
+}
+
+#[test]
+fn clone_keeps_keys() {
+    let mut slab = PartialSlab::<String, 4>::new();
+    let a = slab.insert("a".to_string());
+    let b = slab.insert("b".to_string());
+    slab.remove(a);
+
+    let mut clone = slab.clone();
+    assert_eq!(clone.get(a), None);
+    assert_eq!(clone.get(b).map(String::as_str), Some("b"));
+    // the free list is cloned as well
+    assert_eq!(clone.insert("c".to_string()).index(), a.index());
+}
+
+#[test]
+fn drop_values() {
+    let dropped = AtomicUsize::new(0);
+    let mut slab = PartialSlab::<_, 4>::new();
+    let keys: Vec<_> = (0..4).map(|_| slab.insert(DropCounter(&dropped))).collect();
+
+    drop(slab.remove(keys[1]));
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    let clone = slab.clone();
+    drop(slab);
+    assert_eq!(dropped.load(Ordering::Relaxed), 4);
+    drop(clone);
+    assert_eq!(dropped.load(Ordering::Relaxed), 7);
+}
+
+#[test]
+fn const_new() {
+    static EMPTY: PartialSlab<u8, 4> = PartialSlab::new();
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.capacity(), 4);
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::slice::PartialSlice;
+use crate::PartialArray;
+
+fn append(slice: &mut PartialSlice<u8>, values: &[u8]) {
+    for &value in values {
+        slice.push(value);
+    }
+}
+
+#[test]
+fn view() {
+    let array: PartialArray<u8, 4> = (1..3).collect();
+    let slice = array.as_partial_slice();
+    assert_eq!(slice.capacity(), 4);
+    assert_eq!(slice.len(), 2);
+    assert!(!slice.is_full());
+    assert_eq!(*slice, [1, 2]);
+    assert_eq!(format!("{:?}", slice), "[1, 2]");
+}
+
+#[test]
+fn push_independent_of_capacity() {
+    let mut small = PartialArray::<u8, 2>::default();
+    let mut large = PartialArray::<u8, 16>::default();
+    append(small.as_partial_slice_mut(), &[1, 2]);
+    append(large.as_partial_slice_mut(), &[1, 2, 3]);
+    assert_eq!(small, [1, 2]);
+    assert_eq!(large, [1, 2, 3]);
+    assert!(small.as_partial_slice().is_full());
+}
+
+#[test]
+#[should_panic(expected = "PartialSlice is full (capacity 2)")]
+fn push_full() {
+    let mut array = PartialArray::<u8, 2>::default();
+    append(array.as_partial_slice_mut(), &[1, 2, 3]);
+}
+
+#[test]
+fn try_push() {
+    let mut array = PartialArray::<u8, 1>::default();
+    let slice = array.as_partial_slice_mut();
+    assert!(slice.try_push(1).is_ok());
+    assert_eq!(slice.try_push(2).unwrap_err().element(), 2);
+    assert_eq!(array, [1]);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut array = PartialArray::<u8, 0>::default();
+    let slice = array.as_partial_slice_mut();
+    assert_eq!(slice.capacity(), 0);
+    assert!(slice.is_full());
+    assert!(slice.try_push(1).is_err());
+    assert_eq!(slice.pop(), None);
+}
+
+#[test]
+fn pop_truncate_clear() {
+    let mut array: PartialArray<u8, 8> = (1..7).collect();
+    let slice = array.as_partial_slice_mut();
+    assert_eq!(slice.pop(), Some(6));
+    slice.truncate(3);
+    assert_eq!(*slice, [1, 2, 3]);
+    slice.truncate(5);
+    assert_eq!(*slice, [1, 2, 3]);
+    slice[0] = 42;
+    assert_eq!(array, [42, 2, 3]);
+    array.as_partial_slice_mut().clear();
+    assert!(array.is_empty());
+}
+
+#[test]
+fn elements_are_dropped_once() {
+    let count = AtomicUsize::new(0);
+    let mut array = PartialArray::<_, 4>::default();
+    let slice = array.as_partial_slice_mut();
+    for _ in 0..4 {
+        slice.push(DropCounter(&count));
+    }
+    slice.truncate(2);
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+    drop(slice.pop());
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+    drop(array);
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn eq() {
+    let a: PartialArray<u8, 4> = (1..3).collect();
+    let b: PartialArray<u8, 8> = (1..3).collect();
+    assert_eq!(a.as_partial_slice(), b.as_partial_slice());
+    assert_eq!(*a.as_partial_slice(), [1, 2]);
+}
@@ -0,0 +1,68 @@
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::PartialArray;
+
+#[test]
+fn smallest() {
+    let values = [9, 3, 7, 1, 8, 2, 6, 4, 5, 0];
+    let smallest = PartialArray::<_, 4>::smallest_k(values.iter().copied());
+    assert_eq!(smallest, [0, 1, 2, 3]);
+}
+
+#[test]
+fn fewer_than_n() {
+    let smallest = PartialArray::<_, 8>::smallest_k(vec![3, 1, 2]);
+    assert_eq!(smallest, [1, 2, 3]);
+    let smallest = PartialArray::<u8, 8>::smallest_k(None);
+    assert!(smallest.is_empty());
+}
+
+#[test]
+fn zero_capacity() {
+    let smallest = PartialArray::<_, 0>::smallest_k(0..100);
+    assert!(smallest.is_empty());
+}
+
+#[test]
+fn largest_via_reverse_key() {
+    let largest = PartialArray::<_, 3>::smallest_k_by_key(0..100, |&x| Reverse(x));
+    assert_eq!(largest, [99, 98, 97]);
+}
+
+#[test]
+fn by_comparison() {
+    let words = ["pear", "fig", "banana", "kiwi", "apple"];
+    let shortest = PartialArray::<_, 2>::smallest_k_by(words.iter().copied(), |a, b| {
+        a.len().cmp(&b.len()).then(a.cmp(b))
+    });
+    assert_eq!(shortest, ["fig", "kiwi"]);
+}
+
+#[test]
+fn matches_sorting() {
+    // a simple linear congruential generator for reproducible values
+    let values: Vec<u32> = (0..1000_u32)
+        .scan(42_u32, |state, _| {
+            *state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            Some(*state >> 16)
+        })
+        .collect();
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+
+    let smallest = PartialArray::<_, 10>::smallest_k(values.iter().copied());
+    assert_eq!(smallest[..], sorted[..10]);
+}
+
+#[test]
+fn elements_are_dropped_once() {
+    let count = AtomicUsize::new(0);
+    let values = (0..20).rev().map(|i| (i, DropCounter(&count)));
+    let smallest = PartialArray::<_, 3>::smallest_k_by_key(values, |(i, _)| *i);
+    assert_eq!(count.load(Ordering::Relaxed), 17);
+    assert!(smallest.iter().map(|(i, _)| *i).eq(0..3));
+    drop(smallest);
+    assert_eq!(count.load(Ordering::Relaxed), 20);
+}
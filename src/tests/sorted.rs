@@ -0,0 +1,188 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::sorted::SortedPartialArray;
+use crate::PartialArray;
+
+#[test]
+fn insert_keeps_order() {
+    let mut array = SortedPartialArray::<u8, 8>::new();
+    for (value, index) in [(5, 0), (1, 0), (9, 2), (5, 2), (3, 1)] {
+        assert_eq!(array.insert(value), index);
+    }
+    assert_eq!(array, [1, 3, 5, 5, 9]);
+    assert_eq!(format!("{:?}", array), "[1, 3, 5, 5, 9]");
+}
+
+#[test]
+fn insert_equal_after_existing() {
+    // compare by the first component only
+    #[derive(Debug, PartialEq, Eq)]
+    struct Key(u8, &'static str);
+    impl PartialOrd for Key {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Key {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut array = SortedPartialArray::<Key, 4>::new();
+    array.insert(Key(1, "a"));
+    array.insert(Key(0, "b"));
+    array.insert(Key(1, "c"));
+    let names: Vec<_> = array.iter().map(|key| key.1).collect();
+    assert_eq!(names, ["b", "a", "c"]);
+}
+
+#[test]
+#[should_panic(expected = "SortedPartialArray is full (capacity 2)")]
+fn insert_full() {
+    let mut array: SortedPartialArray<u8, 2> = (0..2).collect();
+    array.insert(3);
+}
+
+#[test]
+fn try_insert_full() {
+    let mut array: SortedPartialArray<u8, 2> = (0..2).collect();
+    assert_eq!(array.try_insert(1).unwrap_err().element(), 1);
+    assert_eq!(array, [0, 1]);
+}
+
+#[test]
+fn contains() {
+    let array: SortedPartialArray<i32, 8> = [4, -2, 8, 0].iter().copied().collect();
+    assert!(array.contains(&-2));
+    assert!(array.contains(&8));
+    assert!(!array.contains(&1));
+    assert!(!SortedPartialArray::<i32, 0>::new().contains(&0));
+}
+
+#[test]
+fn remove() {
+    let mut array: SortedPartialArray<u8, 8> = (0..5).collect();
+    assert_eq!(array.remove(1), 1);
+    assert_eq!(array.remove_value(&3), Some(3));
+    assert_eq!(array.remove_value(&3), None);
+    assert_eq!(array.pop(), Some(4));
+    assert_eq!(array, [0, 2]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn remove_out_of_bounds() {
+    let mut array: SortedPartialArray<u8, 8> = (0..2).collect();
+    array.remove(2);
+}
+
+#[test]
+fn range() {
+    let array: SortedPartialArray<u8, 8> = [1, 3, 3, 5, 7].iter().copied().collect();
+    assert_eq!(array.range(..), [1, 3, 3, 5, 7]);
+    assert_eq!(array.range(3..5), [3, 3]);
+    assert_eq!(array.range(3..=5), [3, 3, 5]);
+    assert_eq!(array.range(2..4), [3, 3]);
+    assert_eq!(array.range(..3), [1]);
+    assert_eq!(array.range(8..), []);
+    #[allow(clippy::reversed_empty_ranges)]
+    let empty = array.range(5..2);
+    assert!(empty.is_empty());
+
+    use std::ops::Bound;
+    assert_eq!(array.range((Bound::Excluded(3), Bound::Unbounded)), [5, 7]);
+}
+
+#[test]
+fn merge() {
+    let a: SortedPartialArray<u8, 8> = [1, 3, 5, 7].iter().copied().collect();
+    let b: SortedPartialArray<u8, 4> = [0, 3, 8].iter().copied().collect();
+    assert_eq!(a.merge(b), [0, 1, 3, 3, 5, 7, 8]);
+
+    let empty = SortedPartialArray::<u8, 4>::new();
+    let c: SortedPartialArray<u8, 4> = (0..4).collect();
+    assert_eq!(c.merge(empty), [0, 1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "merged arrays have more elements than the capacity")]
+fn merge_overflow() {
+    let a: SortedPartialArray<u8, 4> = (0..3).collect();
+    let b: SortedPartialArray<u8, 4> = (0..2).collect();
+    let _ = a.merge(b);
+}
+
+#[test]
+fn try_merge() {
+    let a: SortedPartialArray<u8, 4> = (0..3).collect();
+    let b: SortedPartialArray<u8, 4> = (0..2).collect();
+    let (a, b) = a.try_merge(b).unwrap_err().element();
+    assert_eq!(a, [0, 1, 2]);
+    assert_eq!(b, [0, 1]);
+
+    let merged = a.try_merge(b.into_iter().take(1).collect::<SortedPartialArray<_, 1>>());
+    assert_eq!(merged.unwrap(), [0, 0, 1, 2]);
+}
+
+#[test]
+fn extend() {
+    let mut array: SortedPartialArray<u8, 6> = [4, 2].iter().copied().collect();
+    array.extend([5, 1, 3]);
+    assert_eq!(array, [1, 2, 3, 4, 5]);
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 2 elements to much")]
+fn extend_overflow() {
+    let mut array = SortedPartialArray::<u8, 2>::new();
+    array.extend(0..4);
+}
+
+#[test]
+fn conversions() {
+    let array: PartialArray<u8, 4> = [3, 1, 2].iter().copied().collect();
+    let sorted = SortedPartialArray::from(array);
+    assert_eq!(sorted, &[1, 2, 3][..]);
+    assert_eq!(PartialArray::from(sorted.clone()), [1, 2, 3]);
+    assert_eq!(sorted.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+}
+
+#[test]
+fn drop_elements() {
+    /// A [`DropCounter`] ordered by a key.
+    struct Keyed<'a>(u8, #[allow(dead_code)] DropCounter<'a>);
+    impl PartialEq for Keyed<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Keyed<'_> {}
+    impl PartialOrd for Keyed<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Keyed<'_> {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let dropped = AtomicUsize::new(0);
+    let mut array = SortedPartialArray::<_, 8>::new();
+    for key in [4, 2, 6, 0, 3] {
+        array.insert(Keyed(key, DropCounter(&dropped)));
+    }
+
+    array.remove_value(&Keyed(3, DropCounter(&dropped)));
+    // the removed element and the temporary key
+    assert_eq!(dropped.load(Ordering::Relaxed), 2);
+
+    array.truncate(2);
+    assert_eq!(dropped.load(Ordering::Relaxed), 4);
+
+    drop(array);
+    assert_eq!(dropped.load(Ordering::Relaxed), 6);
+}
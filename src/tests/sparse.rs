@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::sparse::SparseArray;
+use crate::PartialArray;
+
+#[test]
+fn set_get_take() {
+    let mut array = SparseArray::<u8, 8>::new();
+    assert_eq!(array.set(5, 50), None);
+    assert_eq!(array.set(1, 10), None);
+    assert_eq!(array.set(5, 55), Some(50));
+    assert_eq!(array.len(), 2);
+
+    assert_eq!(array.get(5), Some(&55));
+    assert_eq!(array.get(2), None);
+    assert_eq!(array.get(100), None);
+    assert!(array.contains(1));
+    assert!(!array.contains(8));
+    array[1] += 1;
+    assert_eq!(array[1], 11);
+
+    assert_eq!(array.take(5), Some(55));
+    assert_eq!(array.take(5), None);
+    assert_eq!(array.take(100), None);
+    assert_eq!(array.len(), 1);
+}
+
+#[test]
+fn multiple_bitmap_words() {
+    let mut array = SparseArray::<usize, 70, 3>::new();
+    for index in [0, 31, 32, 63, 64, 69] {
+        assert_eq!(array.set(index, index), None);
+    }
+    assert!(array.contains(32));
+    assert!(!array.contains(33));
+    assert!(!array.contains(70));
+    assert_eq!(
+        array.iter().map(|(index, _)| index).collect::<Vec<_>>(),
+        [0, 31, 32, 63, 64, 69]
+    );
+
+    assert_eq!(array.take(31), Some(31));
+    assert_eq!(array.iter_mut().next_back(), Some((69, &mut 69)));
+    assert_eq!(PartialArray::from(array), [0, 32, 63, 64, 69]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn set_out_of_bounds() {
+    let mut array = SparseArray::<u8, 4>::new();
+    array.set(4, 0);
+}
+
+#[test]
+#[should_panic(expected = "slot is vacant or out of bounds")]
+fn index_vacant() {
+    let array = SparseArray::<u8, 4>::new();
+    let _ = array[0];
+}
+
+#[test]
+fn iterate_occupied() {
+    let mut array: SparseArray<u32, 8> = [(6, 60), (0, 0), (3, 30)].iter().copied().collect();
+    let iter = array.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.collect::<Vec<_>>(), [(0, &0), (3, &30), (6, &60)]);
+    assert_eq!(array.iter().next_back(), Some((6, &60)));
+
+    for (index, value) in &mut array {
+        *value += index as u32;
+    }
+    assert_eq!(format!("{:?}", array), "{0: 0, 3: 33, 6: 66}");
+
+    let mut iter = array.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some((6, 66)));
+    assert_eq!(iter.next(), Some((0, 0)));
+    assert_eq!(iter.collect::<Vec<_>>(), [(3, 33)]);
+}
+
+#[test]
+fn into_partial_array() {
+    let array: SparseArray<char, 8> = [(7, 'c'), (2, 'a'), (4, 'b')].iter().copied().collect();
+    let compacted: PartialArray<char, 8> = array.into();
+    assert_eq!(compacted, ['a', 'b', 'c']);
+}
+
+#[test]
+fn get_or_insert_with() {
+    let mut array = SparseArray::<u8, 4>::new();
+    *array.get_or_insert_with(2, || 1) += 1;
+    *array.get_or_insert_with(2, || unreachable!()) += 1;
+    assert_eq!(array.get(2), Some(&3));
+}
+
+#[test]
+fn equality_and_clone() {
+    let a: SparseArray<u8, 4> = [(1, 1), (3, 3)].iter().copied().collect();
+    let b: SparseArray<u8, 4> = [(1, 1), (2, 3)].iter().copied().collect();
+    assert_ne!(a, b);
+    assert_eq!(a, a.clone());
+    assert_eq!(SparseArray::<u8, 4>::new(), SparseArray::default());
+}
+
+#[test]
+fn drop_values() {
+    let dropped = AtomicUsize::new(0);
+    let mut array = SparseArray::<_, 8>::new();
+    for index in [1, 3, 5, 7] {
+        array.set(index, DropCounter(&dropped));
+    }
+
+    // the replaced value
+    drop(array.set(3, DropCounter(&dropped)));
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    let mut iter = array.into_iter();
+    drop(iter.next());
+    assert_eq!(dropped.load(Ordering::Relaxed), 2);
+
+    // the remaining values are dropped with the iterator
+    drop(iter);
+    assert_eq!(dropped.load(Ordering::Relaxed), 5);
+}
@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::DropCounter;
+use crate::spill::SpillArray;
+use crate::PartialArray;
+
+#[test]
+fn inline() {
+    let array: SpillArray<u8, 4> = (0..4).collect();
+    assert!(!array.is_spilled());
+    assert_eq!(array, [0, 1, 2, 3]);
+}
+
+#[test]
+fn spilled() {
+    let array: SpillArray<u8, 4> = (0..5).collect();
+    assert!(array.is_spilled());
+    assert_eq!(array, [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn spilled_without_size_hint() {
+    let array: SpillArray<u8, 4> = (0..10).filter(|x| x % 2 == 0).collect();
+    assert!(array.is_spilled());
+    assert_eq!(array, [0, 2, 4, 6, 8]);
+}
+
+#[test]
+fn extend_spills() {
+    let mut array: SpillArray<u8, 4> = (0..2).collect();
+    array.extend(2..4);
+    assert!(!array.is_spilled());
+    array.extend(4..6);
+    assert!(array.is_spilled());
+    array.extend(6..7);
+    assert_eq!(array, [0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn push_pop() {
+    let mut array = SpillArray::<_, 2>::default();
+    array.push(1);
+    array.push(2);
+    assert!(!array.is_spilled());
+    array.push(3);
+    assert!(array.is_spilled());
+    assert_eq!(array.pop(), Some(3));
+    assert_eq!(array.pop(), Some(2));
+    assert!(array.is_spilled());
+    assert_eq!(array.pop(), Some(1));
+    assert_eq!(array.pop(), None);
+}
+
+#[test]
+fn zero_capacity() {
+    let array: SpillArray<u8, 0> = (0..2).collect();
+    assert!(array.is_spilled());
+    assert_eq!(array, [0, 1]);
+
+    let array: SpillArray<u8, 0> = (0..0).collect();
+    assert!(!array.is_spilled());
+}
+
+#[test]
+fn into_iter() {
+    let array: SpillArray<u8, 4> = (0..3).collect();
+    assert_eq!(array.into_iter().rev().collect::<Vec<_>>(), [2, 1, 0]);
+
+    let array: SpillArray<u8, 4> = (0..6).collect();
+    let iter = array.into_iter();
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn into_vec() {
+    let array = SpillArray::from(PartialArray::<u8, 4>::from([1, 2, 3, 4]));
+    assert_eq!(array.into_vec(), [1, 2, 3, 4]);
+    let array = SpillArray::<u8, 4>::from(vec![1, 2]);
+    assert!(array.is_spilled());
+    assert_eq!(array.into_vec(), [1, 2]);
+}
+
+#[test]
+fn debug() {
+    let array: SpillArray<u8, 2> = (0..3).collect();
+    assert_eq!(format!("{:?}", array), "[0, 1, 2]");
+    let mut iter = array.into_iter();
+    iter.next();
+    assert_eq!(format!("{:?}", iter), "[1, 2]");
+}
+
+#[test]
+fn elements_are_dropped_once() {
+    let count = AtomicUsize::new(0);
+    let array: SpillArray<_, 2> = (0..5).map(|_| DropCounter(&count)).collect();
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+    drop(array);
+    assert_eq!(count.load(Ordering::Relaxed), 5);
+}
@@ -0,0 +1,174 @@
+//! The `loom` tests are only compiled with `--cfg loom` and check all possible
+//! interleavings, e.g. via:
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --release --lib tests::spsc
+//! ```
+use crate::spsc::Queue;
+
+#[cfg(not(loom))]
+mod threads {
+    use super::*;
+    use crate::tests::DropCounter;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    #[test]
+    fn fifo() {
+        let mut queue = Queue::<u8, 3>::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.capacity(), 3);
+        queue.enqueue(1).unwrap();
+        queue.enqueue(2).unwrap();
+        queue.enqueue(3).unwrap();
+        assert!(queue.is_full());
+        assert_eq!(queue.enqueue(4).unwrap_err().element(), 4);
+        assert_eq!(format!("{:?}", queue), "[1, 2, 3]");
+
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(3));
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    #[test]
+    fn wrap_around() {
+        let mut queue = Queue::<usize, 3>::new();
+        queue.enqueue(0).unwrap();
+        for i in 0..10 {
+            queue.enqueue(2 * i + 1).unwrap();
+            queue.enqueue(2 * i + 2).unwrap();
+            assert_eq!(queue.dequeue(), Some(2 * i));
+            assert_eq!(queue.dequeue(), Some(2 * i + 1));
+            assert_eq!(queue.len(), 1);
+        }
+        assert_eq!(format!("{:?}", queue), "[20]");
+    }
+
+    #[test]
+    fn wrap_around_full() {
+        // the counters run modulo `2 * N`, so start at every possible position
+        // and fill the whole queue across the point, where they wrap
+        for start in 0..6 {
+            let mut queue = Queue::<String, 3>::new();
+            for _ in 0..start {
+                queue.enqueue(String::new()).unwrap();
+                queue.dequeue().unwrap();
+            }
+            for round in 0..3 {
+                for i in 0..3 {
+                    queue.enqueue(format!("{}-{}", round, i)).unwrap();
+                }
+                assert!(queue.is_full());
+                assert_eq!(queue.len(), 3);
+                assert_eq!(
+                    format!("{:?}", queue),
+                    format!(r#"["{0}-0", "{0}-1", "{0}-2"]"#, round)
+                );
+                for i in 0..3 {
+                    assert_eq!(queue.dequeue(), Some(format!("{}-{}", round, i)));
+                }
+                assert!(queue.is_empty());
+            }
+            queue.enqueue("dropped".to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut queue = Queue::<u8, 0>::new();
+        assert!(queue.is_full());
+        assert!(queue.enqueue(1).is_err());
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    #[test]
+    fn static_queue() {
+        static QUEUE: Queue<u8, 4> = Queue::new();
+        assert!(QUEUE.is_empty());
+    }
+
+    #[test]
+    fn producer_consumer_threads() {
+        let mut queue = Queue::<usize, 4>::new();
+        let (mut producer, mut consumer) = queue.split();
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..1000 {
+                    let mut value = i;
+                    while let Err(error) = producer.enqueue(value) {
+                        value = error.element();
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            for i in 0..1000 {
+                let value = loop {
+                    match consumer.dequeue() {
+                        Some(value) => break value,
+                        None => thread::yield_now(),
+                    }
+                };
+                assert_eq!(value, i);
+            }
+        });
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn elements_are_dropped_once() {
+        let count = AtomicUsize::new(0);
+        let mut queue = Queue::<_, 4>::new();
+        for _ in 0..6 {
+            queue.enqueue(DropCounter(&count)).ok();
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        drop(queue.dequeue());
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+        drop(queue);
+        assert_eq!(count.load(Ordering::Relaxed), 6);
+    }
+}
+
+#[cfg(loom)]
+mod model {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn producer_consumer() {
+        loom::model(|| {
+            let queue = Arc::new(Queue::<String, 2>::new());
+            let producer = {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    let mut sent = Vec::new();
+                    for i in 0..3 {
+                        // SAFETY: this is the only producer
+                        if unsafe { queue.enqueue_unchecked(i.to_string()) }.is_ok() {
+                            sent.push(i.to_string());
+                        }
+                    }
+                    sent
+                })
+            };
+
+            let consumer = thread::spawn(move || {
+                let mut received = Vec::new();
+                for _ in 0..3 {
+                    // SAFETY: this is the only consumer
+                    if let Some(value) = unsafe { queue.dequeue_unchecked() } {
+                        received.push(value);
+                    }
+                }
+                received
+            });
+            let sent = producer.join().unwrap();
+            let received = consumer.join().unwrap();
+
+            // the received elements are the first sent ones in the same order
+            assert_eq!(received[..], sent[..received.len()]);
+        });
+    }
+}
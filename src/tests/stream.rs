@@ -0,0 +1,53 @@
+use crate::stream::PartialStreamExt;
+use crate::PartialArray;
+use futures::executor::block_on;
+use futures::future::FusedFuture;
+use futures::stream::{self, StreamExt};
+use futures::task::noop_waker_ref;
+use futures::FutureExt;
+use std::task::{Context, Poll};
+
+#[test]
+fn collect() {
+    let array: PartialArray<u32, 4> = block_on(stream::iter(0..3).collect());
+    assert_eq!(array, [0, 1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "Iterator has 1 elements to much")]
+fn collect_overflow() {
+    let _array: PartialArray<u32, 4> = block_on(stream::iter(0..5).collect());
+}
+
+#[test]
+fn collect_partial_exhausted() {
+    let array = block_on(stream::iter(0..3).collect_partial::<4>());
+    assert_eq!(array, [0, 1, 2]);
+}
+
+#[test]
+fn collect_partial_stops_when_full() {
+    let mut polled = 0;
+    let counting = stream::iter(0..).inspect(|_| polled += 1);
+    let array = block_on(counting.collect_partial::<4>());
+    assert_eq!(array, [0, 1, 2, 3]);
+    assert_eq!(polled, 4);
+}
+
+#[test]
+fn collect_partial_pending() {
+    let (sender, receiver) = futures::channel::mpsc::unbounded();
+    let mut future = receiver.collect_partial::<2>();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    sender.unbounded_send(1).unwrap();
+    assert!(future.poll_unpin(&mut cx).is_pending());
+
+    sender.unbounded_send(2).unwrap();
+    sender.unbounded_send(3).unwrap();
+    match future.poll_unpin(&mut cx) {
+        Poll::Ready(array) => assert_eq!(array, [1, 2]),
+        Poll::Pending => panic!("future should be ready"),
+    }
+    assert!(future.is_terminated());
+}
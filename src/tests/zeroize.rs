@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::zeroize::ZeroizingPartialArray;
+use crate::PartialArray;
+use zeroize::Zeroize;
+
+#[derive(Debug)]
+struct ZeroizeCounter<'a>(&'a AtomicUsize);
+impl Zeroize for ZeroizeCounter<'_> {
+    fn zeroize(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn zeroize_wipes_whole_storage() {
+    let mut partial_array: PartialArray<u8, 8> = [0xff; 6].iter().copied().collect();
+    partial_array.truncate(2);
+    partial_array.zeroize();
+
+    assert!(partial_array.is_empty());
+    // SAFETY: all entries were overwritten with zeros, which is a valid `u8`
+    let storage = partial_array
+        .array
+        .map(|entry| unsafe { entry.assume_init() });
+    assert_eq!(storage, [0; 8]);
+}
+
+#[test]
+fn zeroize_elements() {
+    let count = AtomicUsize::new(0);
+
+    let mut partial_array: PartialArray<_, 4> =
+        vec![ZeroizeCounter(&count), ZeroizeCounter(&count)]
+            .into_iter()
+            .collect();
+    partial_array.zeroize();
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn wrapper_drop() {
+    let count = AtomicUsize::new(0);
+
+    let array: ZeroizingPartialArray<_, 4> = vec![ZeroizeCounter(&count), ZeroizeCounter(&count)]
+        .into_iter()
+        .collect();
+    drop(array);
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn wrapper_truncate() {
+    let count = AtomicUsize::new(0);
+
+    let mut array: ZeroizingPartialArray<_, 4> = vec![
+        ZeroizeCounter(&count),
+        ZeroizeCounter(&count),
+        ZeroizeCounter(&count),
+    ]
+    .into_iter()
+    .collect();
+    array.truncate(1);
+    assert_eq!(array.len(), 1);
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn wrapper_pop() {
+    let mut array: ZeroizingPartialArray<u8, 4> = [1, 2, 3].iter().copied().collect();
+    assert_eq!(array.pop(), Some(3));
+    assert_eq!(*array, [1, 2]);
+
+    array.clear();
+    assert_eq!(array.pop(), None);
+}
+
+#[test]
+#[should_panic(expected = "ZeroizingPartialArray is full (capacity 2)")]
+fn wrapper_push() {
+    let mut array = ZeroizingPartialArray::<u8, 2>::default();
+    array.push(1);
+    assert_eq!(array.try_push(2).ok(), Some(()));
+    assert_eq!(array.try_push(3).unwrap_err().element(), 3);
+    assert_eq!(*array, [1, 2]);
+    array.push(3);
+}
+
+#[test]
+fn wrapper_from_wipes_unfilled_storage() {
+    let mut partial_array: PartialArray<u8, 8> = [0xff; 6].iter().copied().collect();
+    partial_array.truncate(2);
+    let array = ZeroizingPartialArray::from(partial_array);
+
+    // SAFETY: the unfilled entries were overwritten with zeros, which is a
+    // valid `u8`
+    let tail: Vec<u8> = array.0.array[2..]
+        .iter()
+        .map(|entry| unsafe { entry.assume_init() })
+        .collect();
+    assert_eq!(tail, [0; 6]);
+    assert_eq!(*array, [0xff, 0xff]);
+}
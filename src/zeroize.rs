@@ -0,0 +1,174 @@
+//! Wiping the storage of [`PartialArray`]s via [`zeroize`].
+//!
+//! This module is only available with the `zeroize` feature. It implements
+//! [`Zeroize`] for [`PartialArray`]s, which wipes the whole backing storage (all
+//! `N` slots, not only the filled ones). As a normal [`PartialArray`] does not
+//! wipe its storage, when elements are removed or dropped, this module also
+//! provides the opt-in [`ZeroizingPartialArray`], which does exactly that. It is
+//! intended for secrets like key material:
+//! ```
+//! # use partial_array::zeroize::ZeroizingPartialArray;
+//! let mut key: ZeroizingPartialArray<u8, 32> = [0x42; 16].iter().copied().collect();
+//! key.truncate(8); // the removed bytes are wiped
+//! assert_eq!(key.len(), 8);
+//! // the whole storage is wiped on drop
+//! ```
+use crate::{CapacityError, PartialArray};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FromIterator;
+use core::ops::{Deref, DerefMut};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+impl<T: Zeroize, const N: usize> Zeroize for PartialArray<T, N> {
+    /// Zeroize and drop all filled elements and wipe the whole storage.
+    ///
+    /// The array is empty afterwards. All `N` slots of the storage are
+    /// overwritten with zeros using volatile writes, so this also wipes
+    /// remainders of previously removed elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::partial_array;
+    /// use zeroize::Zeroize;
+    ///
+    /// let mut array = partial_array![1_u8, 2, 3];
+    /// array.zeroize();
+    /// assert!(array.is_empty());
+    /// ```
+    fn zeroize(&mut self) {
+        self.iter_mut().for_each(Zeroize::zeroize);
+        self.clear();
+        self.array[..].zeroize();
+    }
+}
+
+/// A [`PartialArray`], that wipes removed elements and its storage on drop.
+///
+/// Whenever elements are removed (e.g. via [`pop`] or [`truncate`]), the slots
+/// of the removed elements are wiped. When dropped, the array is [zeroized],
+/// i.e. all `N` slots of the storage are wiped. Wrapping an existing
+/// [`PartialArray`] wipes its unfilled slots, as they may still hold remainders
+/// of previously removed elements.
+///
+/// [`pop`]: ZeroizingPartialArray::pop
+/// [`truncate`]: ZeroizingPartialArray::truncate
+/// [zeroized]: Zeroize::zeroize
+pub struct ZeroizingPartialArray<T: Zeroize, const N: usize>(pub(crate) PartialArray<T, N>);
+impl<T: Zeroize, const N: usize> ZeroizingPartialArray<T, N> {
+    /// Append an element to the back.
+    ///
+    /// # Panics
+    /// Panics, if the array is already full.
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            panic!("ZeroizingPartialArray is full (capacity {})", N);
+        }
+    }
+
+    /// Append an element to the back or return it, if the array is full.
+    ///
+    /// # Example
+    /// ```
+    /// # use partial_array::zeroize::ZeroizingPartialArray;
+    /// let mut key = ZeroizingPartialArray::<u8, 1>::default();
+    ///
+    /// assert!(key.try_push(0x42).is_ok());
+    /// assert_eq!(key.try_push(0x43).unwrap_err().element(), 0x43);
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        self.0.as_partial_slice_mut().try_push(value)
+    }
+
+    /// Remove the last element and return it or [`None`], if it is empty.
+    ///
+    /// The slot of the removed element is wiped.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.0.pop()?;
+        self.0.array[self.0.filled].zeroize();
+        Some(value)
+    }
+
+    /// Shorten the array to the first `len` elements and drop the rest.
+    ///
+    /// The removed elements are zeroized before being dropped and their slots
+    /// are wiped afterwards. This has no effect, if the array has `len` or less
+    /// elements.
+    pub fn truncate(&mut self, len: usize) {
+        let filled = self.0.filled;
+        if len >= filled {
+            return;
+        }
+
+        self.0[len..].iter_mut().for_each(Zeroize::zeroize);
+        self.0.truncate(len);
+        self.0.array[len..filled].zeroize();
+    }
+
+    /// Remove all elements and wipe the whole storage.
+    pub fn clear(&mut self) {
+        self.0.zeroize();
+    }
+}
+impl<T: Zeroize, const N: usize> Drop for ZeroizingPartialArray<T, N> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+impl<T: Zeroize, const N: usize> ZeroizeOnDrop for ZeroizingPartialArray<T, N> {}
+impl<T: Zeroize, const N: usize> Zeroize for ZeroizingPartialArray<T, N> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+impl<T: Zeroize, const N: usize> Default for ZeroizingPartialArray<T, N> {
+    /// Initialize an empty [`ZeroizingPartialArray`].
+    fn default() -> Self {
+        Self(PartialArray::default())
+    }
+}
+impl<T: Zeroize, const N: usize> From<PartialArray<T, N>> for ZeroizingPartialArray<T, N> {
+    /// Wrap a [`PartialArray`] and wipe its unfilled slots.
+    fn from(mut array: PartialArray<T, N>) -> Self {
+        let filled = array.filled;
+        array.array[filled..].zeroize();
+        Self(array)
+    }
+}
+impl<T: Zeroize, const N: usize> Deref for ZeroizingPartialArray<T, N> {
+    type Target = [T];
+
+    /// Dereference to the slice of filled elements (potentially less than `N`).
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<T: Zeroize, const N: usize> DerefMut for ZeroizingPartialArray<T, N> {
+    /// Dereference to the slice of filled elements (potentially less than `N`).
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl<T: Zeroize + Debug, const N: usize> Debug for ZeroizingPartialArray<T, N> {
+    /// Debug-format the slice of filled elements (potentially less than `N`).
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <[T] as Debug>::fmt(self, f)
+    }
+}
+impl<T: Zeroize, const N: usize> FromIterator<T> for ZeroizingPartialArray<T, N> {
+    /// Build up a [`ZeroizingPartialArray`] from an iterator with potentially
+    /// less than `N` elements.
+    ///
+    /// # Panics
+    /// Panics, if the length of the iterator is greater than the maximum length
+    /// of the array (`N`).
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::default();
+        result.extend(iter);
+        result
+    }
+}
+impl<T: Zeroize, const N: usize> Extend<T> for ZeroizingPartialArray<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}